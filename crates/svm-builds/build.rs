@@ -16,6 +16,8 @@ use svm::Releases;
 /// - "macosx-aarch64"
 /// - "windows-amd64"
 /// - "android-aarch64"
+///
+/// as well as `linux-arm64` and Rust target triples, e.g. `aarch64-unknown-linux-gnu`.
 pub const SVM_TARGET_PLATFORM: &str = "SVM_TARGET_PLATFORM";
 
 /// The path to the releases JSON file, that was pre-fetched manually.
@@ -31,7 +33,7 @@ pub const SVM_RELEASES_LIST_JSON: &str = "SVM_RELEASES_LIST_JSON";
 /// Returns the platform to generate the constants for
 ///
 /// if the `SVM_TARGET_PLATFORM` var is set, this will return the matching [svm::Platform],
-/// otherwise the native platform will be used [svm::platform()].
+/// otherwise the platform of [svm::platform()] will be used, which honours `SVM_PLATFORM`.
 fn get_platform() -> svm::Platform {
     if let Ok(s) = std::env::var(SVM_TARGET_PLATFORM) {
         s.parse().unwrap()
@@ -178,6 +180,7 @@ pub static RELEASE_LIST_JSON : &str = r#"{release_json}"#;"##
 
     // Tell Cargo that we need to rerun this if any of the relevant env vars change
     println!("cargo:rerun-if-env-changed={SVM_TARGET_PLATFORM}");
+    println!("cargo:rerun-if-env-changed={}", svm::SVM_PLATFORM);
     println!("cargo:rerun-if-env-changed={SVM_RELEASES_LIST_JSON}");
 }
 
@@ -0,0 +1,131 @@
+use crate::{Artifact, Platform, Releases, SvmError, all_releases};
+use semver::Version;
+use std::collections::BTreeMap;
+
+/// Which Solc versions are available on which platforms, and where they come from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Availability {
+    /// The platforms, in the order of the artifacts of each version.
+    pub platforms: Vec<Platform>,
+    /// The artifact of each version for each of the platforms, if it is available there.
+    pub versions: BTreeMap<Version, Vec<Option<Artifact>>>,
+}
+
+impl Availability {
+    /// Builds the availability matrix from the releases of each platform.
+    pub fn from_releases(releases: &[(Platform, Releases)]) -> Self {
+        let platforms = releases
+            .iter()
+            .map(|(platform, _)| *platform)
+            .collect::<Vec<_>>();
+        let mut versions = BTreeMap::<Version, Vec<Option<Artifact>>>::new();
+        for (index, (platform, releases)) in releases.iter().enumerate() {
+            for version in releases.releases.keys() {
+                let Ok(artifact) = releases.resolve(*platform, version) else {
+                    continue;
+                };
+                versions
+                    .entry(version.clone())
+                    .or_insert_with(|| vec![None; platforms.len()])[index] = Some(artifact);
+            }
+        }
+        Self {
+            platforms,
+            versions,
+        }
+    }
+
+    /// Returns the artifact of a version for a platform, if it is available there.
+    pub fn get(&self, version: &Version, platform: Platform) -> Option<&Artifact> {
+        let index = self.platforms.iter().position(|p| *p == platform)?;
+        self.versions.get(version)?[index].as_ref()
+    }
+
+    /// Returns the versions which are available on all the platforms, in ascending order.
+    pub fn available_everywhere(&self) -> impl Iterator<Item = &Version> {
+        self.versions
+            .iter()
+            .filter(|(_, artifacts)| artifacts.iter().all(Option::is_some))
+            .map(|(version, _)| version)
+    }
+}
+
+/// Blocking version of [`availability`]
+#[cfg(feature = "blocking")]
+pub fn blocking_availability(platforms: &[Platform]) -> Result<Availability, SvmError> {
+    let releases = platforms
+        .iter()
+        .map(|platform| Ok((*platform, crate::blocking_all_releases(*platform)?)))
+        .collect::<Result<Vec<_>, SvmError>>()?;
+    Ok(Availability::from_releases(&releases))
+}
+
+/// Fetches which Solc versions are available on each of the given platforms.
+pub async fn availability(platforms: &[Platform]) -> Result<Availability, SvmError> {
+    let mut releases = Vec::with_capacity(platforms.len());
+    for platform in platforms {
+        releases.push((*platform, all_releases(*platform).await?));
+    }
+    Ok(Availability::from_releases(&releases))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Execution, Source};
+
+    fn releases(versions: &[(&str, &str)]) -> Releases {
+        Releases {
+            releases: versions
+                .iter()
+                .map(|(version, artifact)| (Version::parse(version).unwrap(), artifact.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn availability_matrix() {
+        let linux = releases(&[("0.8.4", "solc-linux-0.8.4"), ("0.8.5", "solc-linux-0.8.5")]);
+        let macos = releases(&[("0.8.4", "solc-macos-0.8.4"), ("0.8.5", "solc-macos-0.8.5")]);
+        // Not supported on Linux aarch64, so dropped.
+        let linux_arm = releases(&[("0.4.26", "solc-arm-0.4.26"), ("0.8.5", "solc-arm-0.8.5")]);
+        let availability = Availability::from_releases(&[
+            (Platform::LinuxAmd64, linux),
+            (Platform::MacOsAarch64, macos),
+            (Platform::LinuxAarch64, linux_arm),
+        ]);
+
+        assert_eq!(
+            availability
+                .versions
+                .keys()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["0.8.4", "0.8.5"]
+        );
+        assert_eq!(
+            availability.available_everywhere().collect::<Vec<_>>(),
+            [&Version::new(0, 8, 5)]
+        );
+
+        let v084 = Version::new(0, 8, 4);
+        let rosetta = availability.get(&v084, Platform::MacOsAarch64).unwrap();
+        assert_eq!(rosetta.execution, Execution::Emulated);
+        assert_eq!(rosetta.source, Source::Official);
+        assert!(availability.get(&v084, Platform::LinuxAarch64).is_none());
+        assert!(availability.get(&v084, Platform::WindowsAmd64).is_none());
+
+        let v085 = Version::new(0, 8, 5);
+        let native = availability.get(&v085, Platform::MacOsAarch64).unwrap();
+        assert_eq!(native.execution, Execution::Native);
+        assert_eq!(native.source, Source::SolcBuilds);
+        assert_eq!(
+            availability
+                .get(&v085, Platform::LinuxAarch64)
+                .unwrap()
+                .source,
+            Source::Nikitastupin
+        );
+    }
+}
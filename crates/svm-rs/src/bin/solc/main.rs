@@ -34,8 +34,8 @@ fn main_() -> anyhow::Result<i32> {
         let version = stripped
             .parse::<semver::Version>()
             .context("failed to parse version specifier")?;
-        if !version.build.is_empty() || !version.pre.is_empty() {
-            anyhow::bail!("version specifier must not have pre-release or build metadata");
+        if !version.pre.is_empty() {
+            anyhow::bail!("version specifier must not have pre-release");
         }
         args.next();
         version
@@ -44,13 +44,12 @@ fn main_() -> anyhow::Result<i32> {
         svm::get_global_version()?.ok_or(svm::SvmError::GlobalVersionNotSet)?
     };
 
-    let bin = svm::version_binary(&version.to_string());
-    if !bin.exists() {
-        anyhow::bail!(
+    let bin = svm::find_version_binary(&version).map_err(|_| {
+        anyhow::anyhow!(
             "Solc version {version} is not installed or does not exist; looked at {}",
-            bin.display()
-        );
-    }
+            svm::version_binary(&version.to_string()).display()
+        )
+    })?;
 
     let mut cmd = Command::new(bin);
     cmd.args(args)
@@ -0,0 +1,64 @@
+use crate::print;
+use clap::Parser;
+use semver::Version;
+
+/// Display where a Solc version comes from, whether it is installed and its known bugs.
+#[derive(Debug, Parser)]
+pub struct InfoCmd {
+    /// The version to describe.
+    version: Version,
+
+    /// Fetch the latest known bugs before listing them.
+    #[arg(long)]
+    refresh_bugs: bool,
+}
+
+impl InfoCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let Self {
+            version,
+            refresh_bugs,
+        } = self;
+        let platform = svm::platform();
+        let releases = svm::all_releases(platform).await?;
+        let release = releases.index(platform).resolve(&version)?;
+        let artifact = &release.artifact;
+
+        println!("Version:  {version}");
+        if let Some(long_version) = &release.long_version {
+            println!("Build:    {long_version}");
+        }
+        println!("Platform: {platform}");
+        println!("Artifact: {}", artifact.name);
+        println!("URL:      {}", artifact.url);
+        println!("SHA-256:  {}", hex::encode_prefixed(&release.sha256));
+        println!("Provider: {} ({})", artifact.source, artifact.trust());
+        println!("Runs:     {}", artifact.execution);
+        let capabilities = svm::capabilities(&version);
+        println!("EVM:      {} (default)", capabilities.default_evm_version);
+        if let (Some(oldest), Some(newest)) = (
+            capabilities.evm_versions.first(),
+            capabilities.evm_versions.last(),
+        ) {
+            println!("Supports: {oldest} - {newest}");
+        }
+        println!(
+            "Via IR:   {}",
+            if capabilities.via_ir { "yes" } else { "no" }
+        );
+        match svm::find_version_binary(&version) {
+            Ok(bin) => {
+                println!("Installed: {}", bin.display());
+                if let Some(origin) = svm::version_origin(&version) {
+                    println!("Downloaded from: {}", origin.url);
+                }
+            }
+            Err(_) => println!("Installed: no"),
+        }
+        if refresh_bugs {
+            svm::refresh_known_bugs().await?;
+        }
+        print::known_bugs(&svm::known_bugs(&version));
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::print;
 use clap::Parser;
 use dialoguer::Input;
 use semver::Version;
+use std::path::PathBuf;
 
 /// Install Solc versions.
 #[derive(Clone, Debug, PartialEq, Eq, Parser)]
@@ -12,10 +13,48 @@ pub struct InstallCmd {
     /// Run in non interactive mode without prompting for user input.
     #[arg(long, default_value_t = false)]
     pub non_interactive: bool,
+
+    /// Run the installed binary to check that it works, removing it again if it doesn't.
+    #[arg(long, default_value_t = false)]
+    pub verify: bool,
+
+    /// Refuse to install community builds, only allowing official Solidity releases.
+    #[arg(long, default_value_t = false)]
+    pub official_only: bool,
+
+    /// Install binaries for this platform into a separate tree, e.g. `linux-aarch64`.
+    #[arg(long, value_name = "PLATFORM")]
+    pub platform: Option<svm::Platform>,
+
+    /// Install binaries into a separate tree under this directory, laid out as
+    /// `{platform}/{version}/solc-{version}`.
+    #[arg(long, value_name = "DIR")]
+    pub root: Option<PathBuf>,
+
+    /// Also install the latest version whose default EVM version is at most this one, e.g.
+    /// `paris` for chains without `PUSH0`.
+    #[arg(long, value_name = "EVM_VERSION")]
+    pub max_evm_version: Option<svm::EvmVersion>,
 }
 
 impl InstallCmd {
-    pub async fn run(self) -> anyhow::Result<()> {
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if let Some(evm_version) = self.max_evm_version {
+            let platform = self.platform.unwrap_or_else(svm::platform);
+            let releases = svm::all_releases(platform).await?;
+            let version = releases
+                .latest_for_evm_version(evm_version)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("no version defaults to EVM version {evm_version} or older")
+                })?;
+            println!("Latest version defaulting to EVM version {evm_version} or older: {version}");
+            self.versions.push(version.to_string());
+        }
+
+        if self.platform.is_some() || self.root.is_some() {
+            return self.install_tree().await;
+        }
+
         let all_versions = svm::all_versions().await?;
 
         for version in self.versions {
@@ -36,11 +75,17 @@ impl InstallCmd {
                 if matches!(input.as_str(), "y" | "Y" | "yes" | "Yes") {
                     svm::set_global_version(&version)?;
                     print::set_global_version(&version);
+                    print::known_bugs_warning(&version);
                 }
-            } else if all_versions.contains(&version) {
+            } else if all_versions.contains(&svm::without_build(&version)) {
                 let spinner = print::installing_version(&version);
-                svm::install(&version).await?;
+                let options = svm::InstallOptions::new()
+                    .smoke_test(self.verify)
+                    .official_only(self.official_only);
+                let solc = svm::install_with_options(&version, &options).await?;
                 spinner.finish_with_message(format!("Downloaded Solc: {version}"));
+                print::binary_warnings(&version, &solc);
+                print::known_bugs_warning(&version);
                 if current_version.is_none() {
                     svm::set_global_version(&version)?;
                     print::set_global_version(&version);
@@ -52,6 +97,30 @@ impl InstallCmd {
 
         Ok(())
     }
+
+    /// Installs the versions into a separate tree, leaving the installed versions and the global
+    /// version alone.
+    async fn install_tree(self) -> anyhow::Result<()> {
+        let mut options = svm::InstallOptions::new()
+            .smoke_test(self.verify)
+            .official_only(self.official_only);
+        if let Some(platform) = self.platform {
+            options = options.platform(platform);
+        }
+        if let Some(root) = self.root {
+            options = options.root(root);
+        }
+
+        for version in self.versions {
+            let version = Version::parse(&version)?;
+            let spinner = print::installing_version(&version);
+            let solc = svm::install_with_options(&version, &options).await?;
+            spinner
+                .finish_with_message(format!("Downloaded Solc: {version} to {}", solc.display()));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -66,7 +135,33 @@ mod tests {
             InstallCmd {
                 versions: vec!["0.8.11".into(), "0.8.10".into()],
                 non_interactive: false,
+                verify: false,
+                official_only: false,
+                platform: None,
+                root: None,
+                max_evm_version: None,
             }
         );
     }
+
+    #[test]
+    fn parse_install_tree() {
+        let args: InstallCmd = InstallCmd::parse_from([
+            "svm",
+            "0.8.20",
+            "--platform",
+            "linux-aarch64",
+            "--root",
+            "/opt/solc",
+        ]);
+        assert_eq!(args.platform, Some(svm::Platform::LinuxAarch64));
+        assert_eq!(args.root, Some(PathBuf::from("/opt/solc")));
+    }
+
+    #[test]
+    fn parse_install_max_evm_version() {
+        let args: InstallCmd = InstallCmd::parse_from(["svm", "--max-evm-version", "paris"]);
+        assert!(args.versions.is_empty());
+        assert_eq!(args.max_evm_version, Some(svm::EvmVersion::Paris));
+    }
 }
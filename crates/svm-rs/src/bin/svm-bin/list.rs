@@ -6,10 +6,21 @@ use semver::Version;
 
 /// List all Solc versions.
 #[derive(Debug, Parser)]
-pub struct ListCmd;
+pub struct ListCmd {
+    /// Show which versions are available on each of these platforms instead, e.g.
+    /// `--platform linux-amd64 --platform macosx-aarch64`.
+    #[arg(long = "platform", value_name = "PLATFORM")]
+    pub platforms: Vec<svm::Platform>,
+}
 
 impl ListCmd {
     pub async fn run(self) -> anyhow::Result<()> {
+        if !self.platforms.is_empty() {
+            let availability = svm::availability(&self.platforms).await?;
+            print::availability(&availability);
+            return Ok(());
+        }
+
         let mut failed = false;
         let mut err = |e: &svm::SvmError, s: &str| {
             failed = true;
@@ -43,6 +54,9 @@ impl ListCmd {
         if !available_versions.is_empty() {
             print::available_versions(available_versions);
         }
+        if !all_versions.is_empty() {
+            print::version_sources(svm::platform(), &all_versions);
+        }
 
         if failed {
             std::process::exit(1);
@@ -10,9 +10,11 @@
 
 use clap::Parser;
 
+mod info;
 mod install;
 mod list;
 mod print;
+mod releases;
 mod remove;
 mod r#use;
 mod utils;
@@ -30,6 +32,8 @@ enum Svm {
     List(list::ListCmd),
     #[command(visible_alias = "i")]
     Install(install::InstallCmd),
+    Info(info::InfoCmd),
+    Releases(releases::ReleasesCmd),
     Use(r#use::UseCmd),
     Which(which::WhichCmd),
     #[command(visible_alias = "rm")]
@@ -45,6 +49,8 @@ async fn main() -> anyhow::Result<()> {
     match opt {
         Svm::List(cmd) => cmd.run().await?,
         Svm::Install(cmd) => cmd.run().await?,
+        Svm::Info(cmd) => cmd.run().await?,
+        Svm::Releases(cmd) => cmd.run().await?,
         Svm::Use(cmd) => cmd.run().await?,
         Svm::Which(cmd) => cmd.run()?,
         Svm::Remove(cmd) => cmd.run().await?,
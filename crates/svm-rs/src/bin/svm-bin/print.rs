@@ -2,7 +2,7 @@ use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use semver::Version;
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
 pub fn current_version(version: Option<Version>) {
     match version {
@@ -38,6 +38,137 @@ pub fn available_versions(versions: Vec<Version>) {
     }
 }
 
+pub fn version_sources(platform: svm::Platform, versions: &[Version]) {
+    println!("\n{}", style("Sources").bold());
+    let groups = versions
+        .iter()
+        .chunk_by(|v| svm::artifact_source(platform, v).zip(svm::artifact_execution(platform, v)))
+        .into_iter()
+        .filter_map(|(source, g)| Some((source?, g.collect::<Vec<_>>())))
+        .collect::<Vec<_>>();
+    for ((source, execution), group) in groups {
+        let range = match group.as_slice() {
+            [only] => only.to_string(),
+            [first, .., last] => format!("{first} - {last}"),
+            [] => continue,
+        };
+        let trust = match source.trust() {
+            svm::Trust::Official => style(source.trust().to_string()).green(),
+            svm::Trust::Community => style(source.trust().to_string()).yellow(),
+        };
+        match execution {
+            svm::Execution::Native => println!("{range}: {source} ({trust})"),
+            svm::Execution::Emulated => println!(
+                "{range}: {source} ({trust}, {})",
+                style("emulated").yellow()
+            ),
+        }
+    }
+}
+
+pub fn availability(availability: &svm::Availability) {
+    let cell = |artifact: &Option<svm::Artifact>| match artifact {
+        Some(artifact) if artifact.execution == svm::Execution::Emulated => {
+            format!("{}, emulated", artifact.source)
+        }
+        Some(artifact) => artifact.source.to_string(),
+        None => "-".to_string(),
+    };
+    let header = std::iter::once("Version".to_string())
+        .chain(availability.platforms.iter().map(ToString::to_string))
+        .collect::<Vec<_>>();
+    let rows = availability
+        .versions
+        .iter()
+        .map(|(version, artifacts)| {
+            std::iter::once(version.to_string())
+                .chain(artifacts.iter().map(cell))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|column| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[column].len())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let line = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", style(line(&header)).bold());
+    for row in &rows {
+        println!("{}", line(row));
+    }
+
+    let everywhere = availability.available_everywhere().collect::<Vec<_>>();
+    match everywhere.last() {
+        Some(latest) => println!(
+            "\n{} versions available on all platforms, latest: {}",
+            everywhere.len(),
+            style(latest.to_string()).green()
+        ),
+        None => println!("\nNo version is available on all platforms"),
+    }
+}
+
+pub fn release_issues(list: &str, issues: &[svm::ReleaseIssue]) {
+    if issues.is_empty() {
+        println!("{}", style(format!("{list}: no issues found")).green());
+        return;
+    }
+    println!("{}", style(list).bold());
+    for issue in issues {
+        println!("{} {issue}", style("-").red());
+    }
+}
+
+pub fn releases_diff(diff: &svm::ReleasesDiff) {
+    if diff.is_empty() {
+        println!("{}", style("No changes").green());
+        return;
+    }
+    for version in &diff.added {
+        println!("{} {version}", style("+").green());
+    }
+    for version in &diff.removed {
+        println!("{} {version}", style("-").red());
+    }
+    for change in &diff.changed {
+        if change.artifact_changed() {
+            println!(
+                "{} {}: artifact {} -> {}",
+                style("~").yellow(),
+                change.version,
+                change.artifact.0,
+                change.artifact.1
+            );
+        }
+        if change.checksum_changed() {
+            let hash = |sha256: &Option<Vec<u8>>| {
+                sha256
+                    .as_ref()
+                    .map_or_else(|| "<none>".to_string(), hex::encode_prefixed)
+            };
+            println!(
+                "{} {}: sha256 {} -> {}",
+                style("!").red().bold(),
+                change.version,
+                hash(&change.sha256.0),
+                hash(&change.sha256.1)
+            );
+        }
+    }
+}
+
 pub fn installing_version(version: &Version) -> ProgressBar {
     let spinner = ProgressBar::new_spinner();
     spinner.enable_steady_tick(Duration::from_millis(120));
@@ -65,3 +196,65 @@ pub fn set_global_version(version: &Version) {
 pub fn version_not_found(version: &Version) {
     println!("{}", style(format!("Version: {version} not found")).red());
 }
+
+pub fn binary_warnings(version: &Version, solc: &Path) {
+    if let Ok(info) = svm::BinaryInfo::from_path(solc)
+        && info.execution(svm::platform()) == Some(svm::Execution::Emulated)
+    {
+        println!(
+            "{}",
+            style(format!(
+                "Note: Solc {version} is built for {info} and runs under emulation"
+            ))
+            .yellow()
+        );
+    }
+    if let Ok(info) = svm::LinkInfo::from_path(solc)
+        && let Some(reason) = info.host_incompatibility()
+    {
+        println!(
+            "{}",
+            style(format!("Warning: Solc {version} may not run: {reason}")).yellow()
+        );
+    }
+}
+
+pub fn known_bugs_warning(version: &Version) {
+    let severe = svm::known_bugs(version)
+        .into_iter()
+        .filter(|bug| bug.severity >= svm::Severity::MediumHigh)
+        .collect::<Vec<_>>();
+    if severe.is_empty() {
+        return;
+    }
+    println!(
+        "{}",
+        style(format!(
+            "Warning: Solc {version} has {} high-severity known bugs, run `svm info {version}` for details",
+            severe.len()
+        ))
+        .yellow()
+    );
+    for bug in &severe {
+        println!("  {} {} ({})", style("-").yellow(), bug.name, bug.severity);
+    }
+}
+
+pub fn known_bugs(bugs: &[svm::Bug]) {
+    if bugs.is_empty() {
+        println!("Known bugs: none");
+        return;
+    }
+    println!("Known bugs:");
+    for bug in bugs {
+        let severity = match bug.severity {
+            severity if severity >= svm::Severity::MediumHigh => style(severity.to_string()).red(),
+            severity if severity >= svm::Severity::Medium => style(severity.to_string()).yellow(),
+            severity => style(severity.to_string()),
+        };
+        println!("  {} ({severity}): {}", bug.name, bug.summary);
+        if let Some(link) = &bug.link {
+            println!("    {link}");
+        }
+    }
+}
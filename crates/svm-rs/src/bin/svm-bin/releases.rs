@@ -0,0 +1,80 @@
+use crate::print;
+use clap::{Parser, Subcommand};
+
+/// Inspect release lists.
+#[derive(Debug, Parser)]
+pub struct ReleasesCmd {
+    #[command(subcommand)]
+    pub cmd: ReleasesSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReleasesSubcommand {
+    /// Check a release list for inconsistencies, e.g. releases without a checksum.
+    Check {
+        /// Path or URL of the release list, e.g. `https://binaries.soliditylang.org/linux-amd64/list.json`.
+        list: String,
+    },
+    /// Report the versions added, removed or changed in a release list, failing if the checksum of
+    /// a release changed.
+    Diff {
+        /// Path or URL of the old release list.
+        old: String,
+        /// Path or URL of the new release list.
+        new: String,
+    },
+}
+
+impl ReleasesCmd {
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self.cmd {
+            ReleasesSubcommand::Check { list } => {
+                let issues = svm::check_release_list(&read_list(&list).await?)?;
+                print::release_issues(&list, &issues);
+                if !issues.is_empty() {
+                    anyhow::bail!("found {} issue(s) in {list}", issues.len());
+                }
+            }
+            ReleasesSubcommand::Diff { old, new } => {
+                let old: svm::Releases = serde_json::from_slice(&read_list(&old).await?)?;
+                let new: svm::Releases = serde_json::from_slice(&read_list(&new).await?)?;
+                let diff = old.diff(&new);
+                print::releases_diff(&diff);
+                let checksum_changes = diff.checksum_changes().count();
+                if checksum_changes > 0 {
+                    anyhow::bail!("the checksum of {checksum_changes} release(s) changed");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads a release list from a URL or a file.
+async fn read_list(list: &str) -> anyhow::Result<Vec<u8>> {
+    if list.starts_with("http://") || list.starts_with("https://") {
+        let res = reqwest::get(list).await?.error_for_status()?;
+        Ok(res.bytes().await?.to_vec())
+    } else {
+        Ok(std::fs::read(list)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_releases() {
+        let args = ReleasesCmd::parse_from(["releases", "check", "list.json"]);
+        assert!(matches!(
+            args.cmd,
+            ReleasesSubcommand::Check { list } if list == "list.json"
+        ));
+        let args = ReleasesCmd::parse_from(["releases", "diff", "old.json", "new.json"]);
+        assert!(matches!(
+            args.cmd,
+            ReleasesSubcommand::Diff { old, new } if old == "old.json" && new == "new.json"
+        ));
+    }
+}
@@ -16,6 +16,7 @@ impl RemoveCmd {
             for v in svm::installed_versions().unwrap_or_default() {
                 svm::remove_version(&v)?;
             }
+            svm::remove_stale_gc_roots()?;
             svm::unset_global_version()?;
             return Ok(());
         } else {
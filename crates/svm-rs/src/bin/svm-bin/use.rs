@@ -20,7 +20,8 @@ impl UseCmd {
         if installed_versions.contains(&version) {
             svm::set_global_version(&version)?;
             print::set_global_version(&version);
-        } else if all_versions.contains(&version) {
+            print::known_bugs_warning(&version);
+        } else if all_versions.contains(&svm::without_build(&version)) {
             println!("Solc {version} is not installed");
             let input: String = Input::new()
                 .with_prompt("Would you like to install it?")
@@ -29,8 +30,10 @@ impl UseCmd {
                 .interact_text()?;
             if matches!(input.as_str(), "y" | "Y" | "yes" | "Yes") {
                 let spinner = print::installing_version(&version);
-                svm::install(&version).await?;
+                let solc = svm::install(&version).await?;
                 spinner.finish_with_message(format!("Downloaded Solc: {version}"));
+                print::binary_warnings(&version, &solc);
+                print::known_bugs_warning(&version);
                 if current_version.is_none() {
                     svm::set_global_version(&version)?;
                     print::set_global_version(&version);
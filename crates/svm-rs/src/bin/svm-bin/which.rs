@@ -11,12 +11,10 @@ pub struct WhichCmd {
 impl WhichCmd {
     pub fn run(self) -> anyhow::Result<()> {
         let Self { version } = self;
-        let bin = svm::version_binary(&version.to_string());
-        if bin.exists() {
-            println!("{}", bin.display());
-        } else {
+        let Ok(bin) = svm::find_version_binary(&version) else {
             return Err(anyhow::anyhow!("version {version} not installed"));
-        }
+        };
+        println!("{}", bin.display());
         Ok(())
     }
 }
@@ -0,0 +1,338 @@
+//! Detection of the executable format and architecture of solc artifacts.
+
+use crate::{SvmError, elf::Elf, platform::Platform};
+use std::{fmt, fs, path::Path};
+
+const EM_386: u16 = 3;
+const EM_ARM: u16 = 40;
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+
+const MH_MAGIC: u32 = 0xfeedface;
+const MH_MAGIC_64: u32 = 0xfeedfacf;
+const FAT_MAGIC: u32 = 0xcafebabe;
+const FAT_MAGIC_64: u32 = 0xcafebabf;
+
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+const CPU_TYPE_X86: u32 = 7;
+const CPU_TYPE_ARM: u32 = 12;
+const CPU_TYPE_X86_64: u32 = CPU_TYPE_X86 | CPU_ARCH_ABI64;
+const CPU_TYPE_ARM64: u32 = CPU_TYPE_ARM | CPU_ARCH_ABI64;
+
+const IMAGE_FILE_MACHINE_I386: u16 = 0x014c;
+const IMAGE_FILE_MACHINE_ARMNT: u16 = 0x01c4;
+const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+const IMAGE_FILE_MACHINE_ARM64: u16 = 0xaa64;
+
+/// Executable file formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryFormat {
+    /// ELF, used by Linux and Android.
+    Elf,
+    /// Mach-O, used by macOS.
+    MachO,
+    /// Portable Executable, used by Windows.
+    Pe,
+}
+
+impl fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Elf => "ELF",
+            Self::MachO => "Mach-O",
+            Self::Pe => "PE",
+        })
+    }
+}
+
+/// CPU architectures of executables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arch {
+    X86,
+    Amd64,
+    Arm,
+    Aarch64,
+    Unknown,
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::X86 => "x86",
+            Self::Amd64 => "amd64",
+            Self::Arm => "arm",
+            Self::Aarch64 => "aarch64",
+            Self::Unknown => "unknown",
+        })
+    }
+}
+
+/// How a binary runs on a platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Execution {
+    /// The binary is built for the platform's architecture.
+    Native,
+    /// The binary is built for another architecture and runs under emulation, e.g. Rosetta 2 on
+    /// macOS or x64 emulation on Windows on ARM.
+    Emulated,
+}
+
+impl fmt::Display for Execution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Native => "native",
+            Self::Emulated => "emulated",
+        })
+    }
+}
+
+/// The executable format and architectures of a binary, as read from its headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BinaryInfo {
+    /// The executable format, which determines the operating system the binary runs on.
+    pub format: BinaryFormat,
+    /// The architectures the binary contains code for.
+    ///
+    /// This has more than one entry only for universal macOS binaries.
+    pub archs: Vec<Arch>,
+}
+
+impl fmt::Display for BinaryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format)?;
+        for (i, arch) in self.archs.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { "+" })?;
+            write!(f, "{arch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl BinaryInfo {
+    /// Reads the executable format and architectures of a binary.
+    pub fn parse(data: &[u8]) -> Result<Self, SvmError> {
+        if data.starts_with(b"\x7fELF") {
+            let arch = match Elf::parse(data)?.machine {
+                EM_386 => Arch::X86,
+                EM_X86_64 => Arch::Amd64,
+                EM_ARM => Arch::Arm,
+                EM_AARCH64 => Arch::Aarch64,
+                _ => Arch::Unknown,
+            };
+            return Ok(Self {
+                format: BinaryFormat::Elf,
+                archs: vec![arch],
+            });
+        }
+
+        if data.starts_with(b"MZ") {
+            return parse_pe(data);
+        }
+
+        let magic = read_u32_be(data, 0)?;
+        if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+            return parse_fat(data, magic == FAT_MAGIC_64);
+        }
+        if [MH_MAGIC, MH_MAGIC_64].contains(&magic) {
+            return Ok(macho(read_u32_be(data, 4)?));
+        }
+        if [MH_MAGIC, MH_MAGIC_64].contains(&magic.swap_bytes()) {
+            return Ok(macho(read_u32_be(data, 4)?.swap_bytes()));
+        }
+
+        Err(SvmError::InvalidBinary(
+            "unrecognized executable format".to_string(),
+        ))
+    }
+
+    /// Reads the executable format and architectures of the binary at `path`.
+    pub fn from_path(path: &Path) -> Result<Self, SvmError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Returns how the binary runs on `platform`, or `None` if it can't run there at all.
+    pub fn execution(&self, platform: Platform) -> Option<Execution> {
+        let (format, native, emulated): (_, &[_], &[_]) = match platform {
+            Platform::LinuxAmd64 => (BinaryFormat::Elf, &[Arch::Amd64, Arch::X86], &[]),
+            Platform::LinuxAarch64 | Platform::AndroidAarch64 => {
+                (BinaryFormat::Elf, &[Arch::Aarch64], &[])
+            }
+            Platform::MacOsAmd64 => (BinaryFormat::MachO, &[Arch::Amd64], &[]),
+            Platform::MacOsAarch64 => (BinaryFormat::MachO, &[Arch::Aarch64], &[Arch::Amd64]),
+            Platform::WindowsAmd64 => (BinaryFormat::Pe, &[Arch::Amd64, Arch::X86], &[]),
+            Platform::WindowsAarch64 => (
+                BinaryFormat::Pe,
+                &[Arch::Aarch64],
+                &[Arch::Amd64, Arch::X86],
+            ),
+            Platform::Unsupported => return None,
+        };
+        if self.format != format {
+            return None;
+        }
+        if self.archs.iter().any(|arch| native.contains(arch)) {
+            Some(Execution::Native)
+        } else if self.archs.iter().any(|arch| emulated.contains(arch)) {
+            Some(Execution::Emulated)
+        } else {
+            None
+        }
+    }
+}
+
+fn macho(cputype: u32) -> BinaryInfo {
+    BinaryInfo {
+        format: BinaryFormat::MachO,
+        archs: vec![macho_arch(cputype)],
+    }
+}
+
+const fn macho_arch(cputype: u32) -> Arch {
+    match cputype {
+        CPU_TYPE_X86 => Arch::X86,
+        CPU_TYPE_X86_64 => Arch::Amd64,
+        CPU_TYPE_ARM => Arch::Arm,
+        CPU_TYPE_ARM64 => Arch::Aarch64,
+        _ => Arch::Unknown,
+    }
+}
+
+/// Parses a universal Mach-O binary, whose headers are always big endian.
+fn parse_fat(data: &[u8], is_64: bool) -> Result<BinaryInfo, SvmError> {
+    let count = read_u32_be(data, 4)? as usize;
+    let entry_size = if is_64 { 32 } else { 20 };
+    let archs = (0..count)
+        .map(|i| read_u32_be(data, 8 + i * entry_size).map(macho_arch))
+        .collect::<Result<_, _>>()?;
+    Ok(BinaryInfo {
+        format: BinaryFormat::MachO,
+        archs,
+    })
+}
+
+/// Parses a PE binary: `e_lfanew` in the DOS header points to the `PE\0\0` signature, followed by
+/// the COFF header's machine type.
+fn parse_pe(data: &[u8]) -> Result<BinaryInfo, SvmError> {
+    let pe = read_u32_le(data, 0x3c)? as usize;
+    if data.get(pe..pe + 4) != Some(b"PE\0\0") {
+        return Err(SvmError::InvalidBinary("missing PE signature".to_string()));
+    }
+    let machine = data
+        .get(pe + 4..pe + 6)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| SvmError::InvalidBinary("unexpected end of file".to_string()))?;
+    let arch = match machine {
+        IMAGE_FILE_MACHINE_I386 => Arch::X86,
+        IMAGE_FILE_MACHINE_AMD64 => Arch::Amd64,
+        IMAGE_FILE_MACHINE_ARMNT => Arch::Arm,
+        IMAGE_FILE_MACHINE_ARM64 => Arch::Aarch64,
+        _ => Arch::Unknown,
+    };
+    Ok(BinaryInfo {
+        format: BinaryFormat::Pe,
+        archs: vec![arch],
+    })
+}
+
+fn read_u32_be(data: &[u8], at: usize) -> Result<u32, SvmError> {
+    data.get(at..at + 4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| SvmError::InvalidBinary("unexpected end of file".to_string()))
+}
+
+fn read_u32_le(data: &[u8], at: usize) -> Result<u32, SvmError> {
+    read_u32_be(data, at).map(u32::swap_bytes)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::elf::tests::Fixture;
+
+    /// Builds the headers of a thin Mach-O binary.
+    pub(crate) fn macho_fixture(cputype: u32) -> Vec<u8> {
+        let mut data = MH_MAGIC_64.to_le_bytes().to_vec();
+        data.extend_from_slice(&cputype.to_le_bytes());
+        data.resize(32, 0);
+        data
+    }
+
+    /// Builds the headers of a universal Mach-O binary.
+    pub(crate) fn fat_fixture(cputypes: &[u32]) -> Vec<u8> {
+        let mut data = FAT_MAGIC.to_be_bytes().to_vec();
+        data.extend_from_slice(&(cputypes.len() as u32).to_be_bytes());
+        for cputype in cputypes {
+            let mut entry = cputype.to_be_bytes().to_vec();
+            entry.resize(20, 0);
+            data.extend_from_slice(&entry);
+        }
+        data
+    }
+
+    /// Builds the headers of a PE binary.
+    pub(crate) fn pe_fixture(machine: u16) -> Vec<u8> {
+        let mut data = vec![0u8; 0x80];
+        data[..2].copy_from_slice(b"MZ");
+        data[0x3c..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        data[0x40..0x44].copy_from_slice(b"PE\0\0");
+        data[0x44..0x46].copy_from_slice(&machine.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parse_formats() {
+        let info = |data: Vec<u8>| BinaryInfo::parse(&data).unwrap();
+
+        let elf = info(Fixture::new().machine(EM_AARCH64).build());
+        assert_eq!(elf.format, BinaryFormat::Elf);
+        assert_eq!(elf.archs, [Arch::Aarch64]);
+
+        let macho = info(macho_fixture(CPU_TYPE_X86_64));
+        assert_eq!(macho.format, BinaryFormat::MachO);
+        assert_eq!(macho.archs, [Arch::Amd64]);
+
+        let fat = info(fat_fixture(&[CPU_TYPE_X86_64, CPU_TYPE_ARM64]));
+        assert_eq!(fat.format, BinaryFormat::MachO);
+        assert_eq!(fat.archs, [Arch::Amd64, Arch::Aarch64]);
+        assert_eq!(fat.to_string(), "Mach-O amd64+aarch64");
+
+        let pe = info(pe_fixture(IMAGE_FILE_MACHINE_AMD64));
+        assert_eq!(pe.format, BinaryFormat::Pe);
+        assert_eq!(pe.archs, [Arch::Amd64]);
+
+        assert!(BinaryInfo::parse(b"PK\x03\x04").is_err());
+        assert!(BinaryInfo::parse(b"MZ").is_err());
+    }
+
+    #[test]
+    fn execution() {
+        let info = |data: Vec<u8>| BinaryInfo::parse(&data).unwrap();
+        let elf_amd64 = info(Fixture::new().build());
+        let elf_aarch64 = info(Fixture::new().machine(EM_AARCH64).build());
+        let macho_amd64 = info(macho_fixture(CPU_TYPE_X86_64));
+        let universal = info(fat_fixture(&[CPU_TYPE_X86_64, CPU_TYPE_ARM64]));
+        let pe_amd64 = info(pe_fixture(IMAGE_FILE_MACHINE_AMD64));
+
+        use Execution::*;
+        assert_eq!(elf_amd64.execution(Platform::LinuxAmd64), Some(Native));
+        assert_eq!(elf_amd64.execution(Platform::LinuxAarch64), None);
+        assert_eq!(elf_aarch64.execution(Platform::LinuxAarch64), Some(Native));
+        assert_eq!(
+            elf_aarch64.execution(Platform::AndroidAarch64),
+            Some(Native)
+        );
+        assert_eq!(elf_amd64.execution(Platform::MacOsAmd64), None);
+
+        assert_eq!(macho_amd64.execution(Platform::MacOsAmd64), Some(Native));
+        assert_eq!(
+            macho_amd64.execution(Platform::MacOsAarch64),
+            Some(Emulated)
+        );
+        assert_eq!(universal.execution(Platform::MacOsAarch64), Some(Native));
+        assert_eq!(universal.execution(Platform::MacOsAmd64), Some(Native));
+
+        assert_eq!(pe_amd64.execution(Platform::WindowsAmd64), Some(Native));
+        assert_eq!(pe_amd64.execution(Platform::WindowsAarch64), Some(Emulated));
+        assert_eq!(pe_amd64.execution(Platform::LinuxAmd64), None);
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{SvmError, mirrors, paths::known_bugs_dir, releases::without_build};
+use semver::Version;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt, fs, sync::LazyLock};
+use url::Url;
+
+/// The list of known bugs published by the Solidity project.
+const BUGS_URL: &str = "https://raw.githubusercontent.com/ethereum/solidity/develop/docs/bugs.json";
+
+/// The known bugs of each released Solidity version.
+const BUGS_BY_VERSION_URL: &str =
+    "https://raw.githubusercontent.com/ethereum/solidity/develop/docs/bugs_by_version.json";
+
+/// The known bugs bundled with svm.
+///
+/// Regenerate them with `cargo test -p svm-rs --lib update_known_bugs -- --ignored`.
+static BUNDLED_BUGS: LazyLock<KnownBugs> = LazyLock::new(|| {
+    KnownBugs::from_json(
+        include_bytes!("../list/bugs.json"),
+        include_bytes!("../list/bugs_by_version.json"),
+    )
+    .expect("could not parse bundled bugs.json")
+});
+
+/// A known bug of the Solidity compiler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bug {
+    /// The unique identifier of the bug, e.g. `SOL-2022-6`.
+    pub uid: String,
+    /// The name of the bug, e.g. `AbiReencodingHeadOverflowWithStaticArrayCleanup`.
+    pub name: String,
+    /// A one-line description of the bug.
+    pub summary: String,
+    /// The detailed description of the bug.
+    #[serde(default)]
+    pub description: String,
+    /// A link to the announcement of the bug.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// The first version with the bug, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introduced: Option<Version>,
+    /// The first version without the bug, if it is fixed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed: Option<Version>,
+    /// How severe the bug is.
+    pub severity: Severity,
+}
+
+impl Bug {
+    /// Returns whether the bug is present in `version` according to the versions it was
+    /// introduced and fixed in.
+    ///
+    /// This ignores the conditions under which the bug occurs, e.g. compiler settings.
+    pub fn affects(&self, version: &Version) -> bool {
+        let version = without_build(version);
+        self.introduced
+            .as_ref()
+            .is_none_or(|introduced| *introduced <= version)
+            && self.fixed.as_ref().is_none_or(|fixed| version < *fixed)
+    }
+}
+
+/// The severity of a [`Bug`], from lowest to highest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    VeryLow,
+    Low,
+    LowMedium,
+    Medium,
+    MediumHigh,
+    High,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::VeryLow => "very low",
+            Self::Low => "low",
+            Self::LowMedium => "low/medium",
+            Self::Medium => "medium",
+            Self::MediumHigh => "medium/high",
+            Self::High => "high",
+        })
+    }
+}
+
+impl Serialize for Severity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Severity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let severity = String::deserialize(deserializer)?;
+        // `bugs.json` spells combined severities both as `low/medium` and `low-medium`.
+        match severity.to_lowercase().replace(['-', '/'], " ").trim() {
+            "very low" => Ok(Self::VeryLow),
+            "low" => Ok(Self::Low),
+            "low medium" => Ok(Self::LowMedium),
+            "medium" => Ok(Self::Medium),
+            "medium high" => Ok(Self::MediumHigh),
+            "high" => Ok(Self::High),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown severity {severity:?}"
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct VersionBugs {
+    bugs: Vec<String>,
+}
+
+/// The known bugs of the Solidity compiler, and the versions they affect.
+#[derive(Clone, Debug, Default)]
+pub struct KnownBugs {
+    bugs: Vec<Bug>,
+    by_version: BTreeMap<Version, VersionBugs>,
+}
+
+impl KnownBugs {
+    /// Parses the `bugs.json` and `bugs_by_version.json` files published by the Solidity project.
+    pub fn from_json(bugs: &[u8], by_version: &[u8]) -> Result<Self, SvmError> {
+        Ok(Self {
+            bugs: serde_json::from_slice(bugs)?,
+            by_version: serde_json::from_slice(by_version)?,
+        })
+    }
+
+    /// Returns the known bugs affecting a version, most severe first.
+    ///
+    /// Released versions are looked up in `bugs_by_version.json`, which takes into account which
+    /// bugs were actually present in each release. Other versions, e.g. pre-releases, are
+    /// matched against the versions each bug was introduced and fixed in.
+    pub fn get(&self, version: &Version) -> Vec<&Bug> {
+        let mut bugs = match self.by_version.get(&without_build(version)) {
+            Some(release) => self
+                .bugs
+                .iter()
+                .filter(|bug| release.bugs.contains(&bug.name))
+                .collect::<Vec<_>>(),
+            None => self
+                .bugs
+                .iter()
+                .filter(|bug| bug.affects(version))
+                .collect(),
+        };
+        bugs.sort_by_key(|bug| std::cmp::Reverse(bug.severity));
+        bugs
+    }
+
+    /// Returns whether there is no data about any bug.
+    pub const fn is_empty(&self) -> bool {
+        self.bugs.is_empty()
+    }
+}
+
+/// Returns the known bugs of a Solc version, most severe first.
+///
+/// The data refreshed with [`refresh_known_bugs`] is used if available, otherwise the data
+/// bundled with svm.
+pub fn known_bugs(version: &Version) -> Vec<Bug> {
+    let refreshed = fs::read(known_bugs_dir().join("bugs.json"))
+        .ok()
+        .zip(fs::read(known_bugs_dir().join("bugs_by_version.json")).ok())
+        .and_then(|(bugs, by_version)| KnownBugs::from_json(&bugs, &by_version).ok());
+    let known_bugs = refreshed.as_ref().unwrap_or(&BUNDLED_BUGS);
+    known_bugs.get(version).into_iter().cloned().collect()
+}
+
+/// Blocking version of [`refresh_known_bugs`]
+#[cfg(feature = "blocking")]
+pub fn blocking_refresh_known_bugs() -> Result<(), SvmError> {
+    let client = reqwest::blocking::Client::new();
+    let get = |url: &str| -> Result<Vec<u8>, SvmError> {
+        let (_, res) = mirrors::blocking_get(&client, &[Url::parse(url)?])?;
+        Ok(res.bytes()?.to_vec())
+    };
+    write_known_bugs(&get(BUGS_URL)?, &get(BUGS_BY_VERSION_URL)?)
+}
+
+/// Fetches the latest known bugs published by the Solidity project, which are used by
+/// [`known_bugs`] from then on.
+pub async fn refresh_known_bugs() -> Result<(), SvmError> {
+    let client = reqwest::Client::new();
+    let (_, bugs) = mirrors::get(&client, &[Url::parse(BUGS_URL)?]).await?;
+    let bugs = bugs.bytes().await?;
+    let (_, by_version) = mirrors::get(&client, &[Url::parse(BUGS_BY_VERSION_URL)?]).await?;
+    let by_version = by_version.bytes().await?;
+    write_known_bugs(&bugs, &by_version)
+}
+
+/// Validates and stores the fetched known bugs.
+fn write_known_bugs(bugs: &[u8], by_version: &[u8]) -> Result<(), SvmError> {
+    KnownBugs::from_json(bugs, by_version)?;
+    fs::create_dir_all(known_bugs_dir())?;
+    fs::write(known_bugs_dir().join("bugs.json"), bugs)?;
+    fs::write(known_bugs_dir().join("bugs_by_version.json"), by_version)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUGS: &str = r#"[
+        {
+            "uid": "SOL-1",
+            "name": "HeadOverflow",
+            "summary": "Head overflow.",
+            "description": "A long description.",
+            "link": "https://blog.soliditylang.org/",
+            "introduced": "0.5.8",
+            "fixed": "0.8.16",
+            "severity": "medium"
+        },
+        {
+            "uid": "SOL-2",
+            "name": "StorageWriteRemoval",
+            "summary": "Storage writes are removed.",
+            "introduced": "0.8.13",
+            "fixed": "0.8.17",
+            "severity": "medium/high"
+        },
+        {
+            "uid": "SOL-3",
+            "name": "DirtyBytes",
+            "summary": "Dirty bytes.",
+            "fixed": "0.8.15",
+            "severity": "low"
+        }
+    ]"#;
+
+    const BUGS_BY_VERSION: &str = r#"{
+        "0.8.13": {"bugs": ["StorageWriteRemoval", "HeadOverflow"], "released": "2022-03-16"},
+        "0.8.16": {"bugs": ["StorageWriteRemoval"], "released": "2022-08-08"},
+        "0.8.17": {"bugs": [], "released": "2022-09-08"}
+    }"#;
+
+    fn names(bugs: Vec<&Bug>) -> Vec<&str> {
+        bugs.into_iter().map(|bug| bug.name.as_str()).collect()
+    }
+
+    #[test]
+    fn bugs_of_version() {
+        let known_bugs = KnownBugs::from_json(BUGS.as_bytes(), BUGS_BY_VERSION.as_bytes()).unwrap();
+        let v = |version: &str| Version::parse(version).unwrap();
+
+        // Released versions are looked up by version, most severe first.
+        assert_eq!(
+            names(known_bugs.get(&v("0.8.13"))),
+            ["StorageWriteRemoval", "HeadOverflow"]
+        );
+        assert_eq!(
+            names(known_bugs.get(&v("0.8.16+commit.07a7930e"))),
+            ["StorageWriteRemoval"]
+        );
+        assert!(known_bugs.get(&v("0.8.17")).is_empty());
+
+        // Other versions are matched against the affected range.
+        assert_eq!(
+            names(known_bugs.get(&v("0.8.14"))),
+            ["StorageWriteRemoval", "HeadOverflow", "DirtyBytes"]
+        );
+        assert_eq!(
+            names(known_bugs.get(&v("0.8.16-pre.1"))),
+            ["StorageWriteRemoval", "HeadOverflow"]
+        );
+        assert!(known_bugs.get(&v("0.8.30")).is_empty());
+    }
+
+    #[test]
+    fn severities() {
+        for (json, severity) in [
+            (r#""very low""#, Severity::VeryLow),
+            (r#""low-medium""#, Severity::LowMedium),
+            (r#""medium/high""#, Severity::MediumHigh),
+            (r#""High""#, Severity::High),
+        ] {
+            assert_eq!(serde_json::from_str::<Severity>(json).unwrap(), severity);
+        }
+        assert!(serde_json::from_str::<Severity>(r#""critical""#).is_err());
+        assert!(Severity::MediumHigh > Severity::Medium);
+    }
+
+    #[test]
+    fn bundled_bugs() {
+        // Bugs must not reference unknown versions or fail to parse.
+        let _ = &*BUNDLED_BUGS;
+    }
+
+    #[test]
+    #[ignore = "the bundled lists are placeholders until regenerated with `update_known_bugs`"]
+    fn bundled_bugs_are_populated() {
+        let bugs = BUNDLED_BUGS.get(&Version::new(0, 8, 13));
+        assert!(!bugs.is_empty());
+        assert!(bugs.iter().all(|bug| bug.affects(&Version::new(0, 8, 13))));
+    }
+
+    /// Regenerates the bundled `list/bugs.json` and `list/bugs_by_version.json`.
+    #[tokio::test]
+    #[ignore = "fetches the known bugs from GitHub"]
+    async fn update_known_bugs() {
+        for (url, file) in [
+            (BUGS_URL, "bugs.json"),
+            (BUGS_BY_VERSION_URL, "bugs_by_version.json"),
+        ] {
+            let json = reqwest::get(url).await.unwrap().bytes().await.unwrap();
+            let path = format!("{}/list/{file}", env!("CARGO_MANIFEST_DIR"));
+            fs::write(path, json).unwrap();
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{Releases, SvmError, releases::without_build};
+use semver::Version;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use std::{fmt, str::FromStr};
+
+/// An EVM version that Solc can target with `--evm-version`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EvmVersion {
+    Homestead,
+    TangerineWhistle,
+    SpuriousDragon,
+    Byzantium,
+    Constantinople,
+    Petersburg,
+    Istanbul,
+    Berlin,
+    London,
+    Paris,
+    Shanghai,
+    Cancun,
+    Prague,
+    Osaka,
+}
+
+impl EvmVersion {
+    /// All EVM versions, from oldest to newest.
+    pub const ALL: [Self; 14] = [
+        Self::Homestead,
+        Self::TangerineWhistle,
+        Self::SpuriousDragon,
+        Self::Byzantium,
+        Self::Constantinople,
+        Self::Petersburg,
+        Self::Istanbul,
+        Self::Berlin,
+        Self::London,
+        Self::Paris,
+        Self::Shanghai,
+        Self::Cancun,
+        Self::Prague,
+        Self::Osaka,
+    ];
+
+    /// Returns the name Solc uses for the EVM version, e.g. `tangerineWhistle`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Homestead => "homestead",
+            Self::TangerineWhistle => "tangerineWhistle",
+            Self::SpuriousDragon => "spuriousDragon",
+            Self::Byzantium => "byzantium",
+            Self::Constantinople => "constantinople",
+            Self::Petersburg => "petersburg",
+            Self::Istanbul => "istanbul",
+            Self::Berlin => "berlin",
+            Self::London => "london",
+            Self::Paris => "paris",
+            Self::Shanghai => "shanghai",
+            Self::Cancun => "cancun",
+            Self::Prague => "prague",
+            Self::Osaka => "osaka",
+        }
+    }
+}
+
+impl fmt::Display for EvmVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EvmVersion {
+    type Err = SvmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `merge` is the name Solc 0.8.18 - 0.8.20 accepted as an alias of `paris`.
+        if s.eq_ignore_ascii_case("merge") {
+            return Ok(Self::Paris);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|evm_version| evm_version.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| SvmError::UnknownEvmVersion(s.to_string()))
+    }
+}
+
+impl Serialize for EvmVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EvmVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// What a Solc version supports, as documented in the Solidity changelog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The EVM version targeted when `--evm-version` isn't given.
+    ///
+    /// Versions before 0.4.21 have no `--evm-version` flag and always target
+    /// [`EvmVersion::Homestead`].
+    pub default_evm_version: EvmVersion,
+    /// The EVM versions accepted by `--evm-version`, from oldest to newest.
+    ///
+    /// This is empty for versions before 0.4.21.
+    pub evm_versions: Vec<EvmVersion>,
+    /// Whether code generation through the IR is available without the `experimental` prefix,
+    /// i.e. with `--via-ir` and the `viaIR` setting.
+    pub via_ir: bool,
+    /// Whether the optimizer steps can be toggled through the `optimizer.details` setting.
+    pub optimizer_details: bool,
+}
+
+impl Capabilities {
+    /// Returns whether `--evm-version` accepts `evm_version`.
+    pub fn supports(&self, evm_version: EvmVersion) -> bool {
+        self.evm_versions.contains(&evm_version)
+    }
+}
+
+/// A numeric Solc version, comparable without pre-releases and build metadata.
+type SolcVersion = (u64, u64, u64);
+
+/// The first version to accept each EVM version, and the first version to default to it.
+///
+/// Sorted by EVM version.
+const EVM_VERSIONS: &[(EvmVersion, SolcVersion, Option<SolcVersion>)] = &[
+    (EvmVersion::Homestead, (0, 4, 21), None),
+    (EvmVersion::TangerineWhistle, (0, 4, 21), None),
+    (EvmVersion::SpuriousDragon, (0, 4, 21), None),
+    (EvmVersion::Byzantium, (0, 4, 21), Some((0, 4, 21))),
+    (EvmVersion::Constantinople, (0, 4, 21), None),
+    (EvmVersion::Petersburg, (0, 5, 5), Some((0, 5, 5))),
+    (EvmVersion::Istanbul, (0, 5, 13), Some((0, 5, 14))),
+    (EvmVersion::Berlin, (0, 8, 5), Some((0, 8, 5))),
+    (EvmVersion::London, (0, 8, 7), Some((0, 8, 7))),
+    (EvmVersion::Paris, (0, 8, 18), Some((0, 8, 18))),
+    (EvmVersion::Shanghai, (0, 8, 20), Some((0, 8, 20))),
+    (EvmVersion::Cancun, (0, 8, 24), Some((0, 8, 25))),
+    (EvmVersion::Prague, (0, 8, 27), Some((0, 8, 30))),
+    (EvmVersion::Osaka, (0, 8, 29), Some((0, 8, 31))),
+];
+
+/// Returns the capabilities of a Solc version.
+///
+/// Pre-releases are treated as the version they precede, so `0.8.31-pre.1` is assumed to
+/// support what 0.8.31 supports.
+pub fn capabilities(version: &Version) -> Capabilities {
+    let version = without_build(version);
+    let version = (version.major, version.minor, version.patch);
+    let evm_versions = EVM_VERSIONS
+        .iter()
+        .filter(|(_, supported, _)| version >= *supported)
+        .map(|(evm_version, ..)| *evm_version)
+        .collect();
+    let default_evm_version = EVM_VERSIONS
+        .iter()
+        .rev()
+        .find(|(.., default)| default.is_some_and(|default| version >= default))
+        .map_or(EvmVersion::Homestead, |(evm_version, ..)| *evm_version);
+    Capabilities {
+        default_evm_version,
+        evm_versions,
+        via_ir: version >= (0, 8, 13),
+        optimizer_details: version >= (0, 5, 5),
+    }
+}
+
+impl Releases {
+    /// Returns the latest release whose default EVM version is at most `evm_version`, e.g. the
+    /// latest Solc that doesn't emit `PUSH0` unless asked to with `paris`.
+    pub fn latest_for_evm_version(&self, evm_version: EvmVersion) -> Option<&Version> {
+        self.releases
+            .keys()
+            .rev()
+            .find(|version| capabilities(version).default_evm_version <= evm_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn evm_version_names() {
+        for evm_version in EvmVersion::ALL {
+            assert_eq!(
+                evm_version.to_string().parse::<EvmVersion>().unwrap(),
+                evm_version
+            );
+        }
+        assert_eq!("merge".parse::<EvmVersion>().unwrap(), EvmVersion::Paris);
+        assert_eq!(
+            "TangerineWhistle".parse::<EvmVersion>().unwrap(),
+            EvmVersion::TangerineWhistle
+        );
+        assert!("frontier".parse::<EvmVersion>().is_err());
+    }
+
+    #[test]
+    fn version_capabilities() {
+        let old = capabilities(&v("0.4.20"));
+        assert_eq!(old.default_evm_version, EvmVersion::Homestead);
+        assert!(old.evm_versions.is_empty());
+        assert!(!old.optimizer_details);
+
+        let byzantium = capabilities(&v("0.5.4"));
+        assert_eq!(byzantium.default_evm_version, EvmVersion::Byzantium);
+        assert_eq!(
+            byzantium.evm_versions.last(),
+            Some(&EvmVersion::Constantinople)
+        );
+
+        // Istanbul was supported a release before it became the default.
+        let istanbul = capabilities(&v("0.5.13"));
+        assert_eq!(istanbul.default_evm_version, EvmVersion::Petersburg);
+        assert!(istanbul.supports(EvmVersion::Istanbul));
+
+        let paris = capabilities(&v("0.8.19+commit.7dd6d404"));
+        assert_eq!(paris.default_evm_version, EvmVersion::Paris);
+        assert!(!paris.supports(EvmVersion::Shanghai));
+        assert!(paris.via_ir && paris.optimizer_details);
+
+        assert_eq!(
+            capabilities(&v("0.8.20")).default_evm_version,
+            EvmVersion::Shanghai
+        );
+        assert_eq!(
+            capabilities(&v("0.8.31-pre.1")).default_evm_version,
+            EvmVersion::Osaka
+        );
+        assert!(!capabilities(&v("0.8.12")).via_ir);
+    }
+
+    #[test]
+    fn latest_for_evm_version() {
+        let releases: Releases = serde_json::from_str(
+            r#"{
+                "builds": [],
+                "releases": {
+                    "0.8.17": "solc-v0.8.17",
+                    "0.8.19": "solc-v0.8.19",
+                    "0.8.20": "solc-v0.8.20",
+                    "0.8.24": "solc-v0.8.24"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            releases.latest_for_evm_version(EvmVersion::Paris),
+            Some(&v("0.8.19"))
+        );
+        assert_eq!(
+            releases.latest_for_evm_version(EvmVersion::Cancun),
+            Some(&v("0.8.24"))
+        );
+        assert_eq!(
+            releases.latest_for_evm_version(EvmVersion::London),
+            Some(&v("0.8.17"))
+        );
+        assert_eq!(releases.latest_for_evm_version(EvmVersion::Berlin), None);
+    }
+}
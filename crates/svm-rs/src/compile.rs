@@ -0,0 +1,423 @@
+use crate::{EvmVersion, Runner, SvmError, find_version_binary};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, fmt, path::Path};
+
+/// The input of `solc --standard-json`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardJsonInput {
+    /// The source language, `Solidity` or `Yul`.
+    pub language: String,
+    /// The source files by name.
+    pub sources: BTreeMap<String, SourceFile>,
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+impl StandardJsonInput {
+    /// Creates a Solidity input compiling `sources`, selecting the ABI and bytecode of every
+    /// contract.
+    pub fn solidity(sources: impl IntoIterator<Item = (String, String)>) -> Self {
+        let output_selection = [(
+            "*".to_string(),
+            [(
+                "*".to_string(),
+                vec![
+                    "abi".to_string(),
+                    "evm.bytecode.object".to_string(),
+                    "evm.deployedBytecode.object".to_string(),
+                ],
+            )]
+            .into(),
+        )]
+        .into();
+        Self {
+            language: "Solidity".to_string(),
+            sources: sources
+                .into_iter()
+                .map(|(name, content)| (name, SourceFile::new(content)))
+                .collect(),
+            settings: Settings {
+                output_selection,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// A source file of a [`StandardJsonInput`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceFile {
+    /// The contents of the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// URLs the file can be read from, instead of `content`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+}
+
+impl SourceFile {
+    /// Creates a source file with the given contents.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: Some(content.into()),
+            urls: Vec::new(),
+        }
+    }
+}
+
+/// The compiler settings of a [`StandardJsonInput`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer: Option<Optimizer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm_version: Option<EvmVersion>,
+    #[serde(default, rename = "viaIR", skip_serializing_if = "Option::is_none")]
+    pub via_ir: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remappings: Vec<String>,
+    /// The outputs to generate, by file name and contract name, e.g. `*` and `*` to `abi`.
+    #[serde(default)]
+    pub output_selection: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    /// Any other setting, e.g. `libraries` or `metadata`.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The optimizer settings of a [`StandardJsonInput`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Optimizer {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<u64>,
+    /// The optimizer steps to toggle, available since 0.5.5.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+/// The output of `solc --standard-json`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StandardJsonOutput {
+    /// The errors, warnings and infos reported by the compiler.
+    #[serde(default)]
+    pub errors: Vec<CompilerError>,
+    /// The compiled source files by name.
+    #[serde(default)]
+    pub sources: BTreeMap<String, SourceOutput>,
+    /// The compiled contracts by file name and contract name.
+    #[serde(default)]
+    pub contracts: BTreeMap<String, BTreeMap<String, Contract>>,
+}
+
+impl StandardJsonOutput {
+    /// Returns the reported errors.
+    pub fn errors(&self) -> impl Iterator<Item = &CompilerError> {
+        self.errors
+            .iter()
+            .filter(|error| error.severity == ErrorSeverity::Error)
+    }
+
+    /// Returns the reported warnings.
+    pub fn warnings(&self) -> impl Iterator<Item = &CompilerError> {
+        self.errors
+            .iter()
+            .filter(|error| error.severity == ErrorSeverity::Warning)
+    }
+
+    /// Returns whether the compiler reported any error, in which case the output is incomplete.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+}
+
+/// An error, warning or info reported by the compiler.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerError {
+    pub severity: ErrorSeverity,
+    /// The kind of error, e.g. `TypeError` or `Warning`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The compiler component that reported it, e.g. `general`.
+    #[serde(default)]
+    pub component: String,
+    pub message: String,
+    /// The message with its source location, as printed on the command line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_location: Option<SourceLocation>,
+}
+
+impl fmt::Display for CompilerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.formatted_message {
+            Some(message) => f.write_str(message.trim_end()),
+            None => write!(f, "{}: {}", self.kind, self.message),
+        }
+    }
+}
+
+/// The severity of a [`CompilerError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// The range of a source file a [`CompilerError`] refers to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub file: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// A compiled source file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SourceOutput {
+    pub id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ast: Option<Value>,
+}
+
+/// A compiled contract.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Contract {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abi: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evm: Option<Evm>,
+    /// Any other selected output, e.g. `metadata` or `storageLayout`.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The EVM outputs of a compiled contract.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Evm {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytecode: Option<Bytecode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_bytecode: Option<Bytecode>,
+    /// Any other selected output, e.g. `methodIdentifiers` or `gasEstimates`.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// The bytecode of a compiled contract.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bytecode {
+    /// The hex-encoded bytecode, with placeholders for unlinked libraries.
+    #[serde(default)]
+    pub object: String,
+    /// Any other selected output, e.g. `sourceMap` or `linkReferences`.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Blocking version of [`compile`]
+#[cfg(feature = "blocking")]
+pub fn blocking_compile(
+    version: &Version,
+    input: &StandardJsonInput,
+) -> Result<StandardJsonOutput, SvmError> {
+    let solc_path = match find_version_binary(version) {
+        Err(SvmError::VersionNotInstalled(_)) => crate::blocking_install(version)?,
+        solc_path => solc_path?,
+    };
+    compile_with(&solc_path, input)
+}
+
+/// Compiles `input` with Solc `version`, installing it first if needed.
+///
+/// Compiler errors are part of the output, see [`StandardJsonOutput::has_errors`]. An error is
+/// only returned if the compiler couldn't be installed or run, or didn't produce an output.
+///
+/// The compiler runs on a blocking thread of the Tokio runtime.
+pub async fn compile(
+    version: &Version,
+    input: &StandardJsonInput,
+) -> Result<StandardJsonOutput, SvmError> {
+    let solc_path = match find_version_binary(version) {
+        Err(SvmError::VersionNotInstalled(_)) => crate::install(version).await?,
+        solc_path => solc_path?,
+    };
+    let input = input.clone();
+    tokio::task::spawn_blocking(move || compile_with(&solc_path, &input))
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+}
+
+/// Compiles `input` with the Solc binary at `solc_path`.
+pub fn compile_with(
+    solc_path: &Path,
+    input: &StandardJsonInput,
+) -> Result<StandardJsonOutput, SvmError> {
+    compile_with_runner(&Runner::new(solc_path), input)
+}
+
+/// Compiles `input` with a [`Runner`], enforcing its limits.
+pub fn compile_with_runner(
+    runner: &Runner,
+    input: &StandardJsonInput,
+) -> Result<StandardJsonOutput, SvmError> {
+    let failed = |reason: String| SvmError::CompilationFailed {
+        path: runner.solc_path().display().to_string(),
+        reason,
+    };
+
+    let output = runner.run(["--standard-json"], &serde_json::to_vec(input)?)?;
+    if !output.status.success() {
+        return Err(failed(format!(
+            "exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|err| failed(format!("could not parse the output: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"{
+        "errors": [
+            {
+                "component": "general",
+                "errorCode": "1878",
+                "formattedMessage": "Warning: SPDX license identifier not provided in source file.\n",
+                "message": "SPDX license identifier not provided in source file.",
+                "severity": "warning",
+                "sourceLocation": {"end": -1, "file": "A.sol", "start": -1},
+                "type": "Warning"
+            }
+        ],
+        "sources": {"A.sol": {"id": 0}},
+        "contracts": {
+            "A.sol": {
+                "A": {
+                    "abi": [],
+                    "evm": {
+                        "bytecode": {"object": "6080", "sourceMap": "1:2:0"},
+                        "deployedBytecode": {"object": "6080"},
+                        "methodIdentifiers": {}
+                    },
+                    "metadata": "{}"
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn standard_json_input() {
+        let mut input =
+            StandardJsonInput::solidity([("A.sol".to_string(), "contract A {}".to_string())]);
+        input.settings.evm_version = Some(EvmVersion::Paris);
+        input.settings.optimizer = Some(Optimizer {
+            enabled: true,
+            runs: Some(200),
+            details: None,
+        });
+        input.settings.other.insert(
+            "metadata".to_string(),
+            serde_json::json!({"bytecodeHash": "none"}),
+        );
+
+        let json = serde_json::to_value(&input).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "language": "Solidity",
+                "sources": {"A.sol": {"content": "contract A {}"}},
+                "settings": {
+                    "optimizer": {"enabled": true, "runs": 200},
+                    "evmVersion": "paris",
+                    "outputSelection": {
+                        "*": {"*": ["abi", "evm.bytecode.object", "evm.deployedBytecode.object"]}
+                    },
+                    "metadata": {"bytecodeHash": "none"}
+                }
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<StandardJsonInput>(json).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn standard_json_output() {
+        let output: StandardJsonOutput = serde_json::from_str(OUTPUT).unwrap();
+        assert!(!output.has_errors());
+        let warning = output.warnings().next().unwrap();
+        assert_eq!(warning.error_code.as_deref(), Some("1878"));
+        assert_eq!(
+            warning.to_string(),
+            "Warning: SPDX license identifier not provided in source file."
+        );
+
+        let contract = &output.contracts["A.sol"]["A"];
+        let evm = contract.evm.as_ref().unwrap();
+        assert_eq!(evm.bytecode.as_ref().unwrap().object, "6080");
+        assert!(evm.other.contains_key("methodIdentifiers"));
+        assert!(contract.other.contains_key("metadata"));
+
+        let output: StandardJsonOutput = serde_json::from_str(
+            r#"{"errors": [{"severity": "error", "type": "ParserError", "component": "general", "message": "Expected pragma"}]}"#,
+        )
+        .unwrap();
+        assert!(output.has_errors());
+        assert_eq!(
+            output.errors().next().unwrap().to_string(),
+            "ParserError: Expected pragma"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn compile_with_binary() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let solc_path = dir.path().join("solc");
+        let input_path = dir.path().join("input.json");
+        fs::write(
+            &solc_path,
+            format!(
+                "#!/bin/sh\n[ \"$1\" = --standard-json ] || exit 1\ncat > '{}'\ncat <<'EOF'\n{OUTPUT}\nEOF\n",
+                input_path.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&solc_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let input =
+            StandardJsonInput::solidity([("A.sol".to_string(), "contract A {}".to_string())]);
+        let output = compile_with(&solc_path, &input).unwrap();
+        assert_eq!(output.contracts["A.sol"].len(), 1);
+        let written: StandardJsonInput =
+            serde_json::from_slice(&fs::read(&input_path).unwrap()).unwrap();
+        assert_eq!(written, input);
+
+        fs::write(&solc_path, "#!/bin/sh\necho 'out of memory' >&2\nexit 3\n").unwrap();
+        let err = compile_with(&solc_path, &input).unwrap_err();
+        assert!(err.to_string().contains("out of memory"), "{err}");
+
+        fs::write(&solc_path, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        let runner = Runner::new(&solc_path).timeout(std::time::Duration::from_millis(200));
+        let err = compile_with_runner(&runner, &input).unwrap_err();
+        assert!(matches!(err, SvmError::Timeout(..)), "{err}");
+    }
+}
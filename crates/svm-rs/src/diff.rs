@@ -0,0 +1,150 @@
+use crate::Releases;
+use semver::Version;
+
+/// The differences between two release lists, see [`Releases::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReleasesDiff {
+    /// Versions only released in the new list.
+    pub added: Vec<Version>,
+    /// Versions only released in the old list.
+    pub removed: Vec<Version>,
+    /// Versions released in both lists with another artifact or checksum.
+    pub changed: Vec<ReleaseChange>,
+}
+
+impl ReleasesDiff {
+    /// Returns whether both lists release the same artifacts.
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Returns the changes of released artifacts' checksums.
+    ///
+    /// A published release is never rebuilt, so these point to a compromised or broken mirror.
+    pub fn checksum_changes(&self) -> impl Iterator<Item = &ReleaseChange> {
+        self.changed
+            .iter()
+            .filter(|change| change.checksum_changed())
+    }
+}
+
+/// A version released in both lists with another artifact or checksum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseChange {
+    pub version: Version,
+    /// The artifact name in the old and the new list.
+    pub artifact: (String, String),
+    /// The SHA-256 checksum in the old and the new list, if the list has a build for the version.
+    pub sha256: (Option<Vec<u8>>, Option<Vec<u8>>),
+}
+
+impl ReleaseChange {
+    /// Returns whether the artifact was renamed.
+    pub fn artifact_changed(&self) -> bool {
+        self.artifact.0 != self.artifact.1
+    }
+
+    /// Returns whether the checksum changed.
+    pub fn checksum_changed(&self) -> bool {
+        self.sha256.0 != self.sha256.1
+    }
+}
+
+impl Releases {
+    /// Returns the versions added, removed or changed in `new` compared to this list.
+    pub fn diff(&self, new: &Self) -> ReleasesDiff {
+        let mut diff = ReleasesDiff::default();
+        for (version, artifact) in &self.releases {
+            let Some(new_artifact) = new.releases.get(version) else {
+                diff.removed.push(version.clone());
+                continue;
+            };
+            let change = ReleaseChange {
+                version: version.clone(),
+                artifact: (artifact.clone(), new_artifact.clone()),
+                sha256: (self.get_checksum(version), new.get_checksum(version)),
+            };
+            if change.artifact_changed() || change.checksum_changed() {
+                diff.changed.push(change);
+            }
+        }
+        diff.added = new
+            .releases
+            .keys()
+            .filter(|version| !self.releases.contains_key(*version))
+            .cloned()
+            .collect();
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildInfo;
+
+    fn list(releases: &[(&str, &str, u8)]) -> Releases {
+        let build = |version: &str, artifact: &str, sha256: u8| BuildInfo {
+            version: version.parse().unwrap(),
+            sha256: vec![sha256; 32],
+            path: Some(artifact.to_string()),
+            prerelease: None,
+            build: None,
+            long_version: None,
+            keccak256: None,
+            urls: vec![],
+        };
+        Releases {
+            builds: releases
+                .iter()
+                .map(|(version, artifact, sha256)| build(version, artifact, *sha256))
+                .collect(),
+            releases: releases
+                .iter()
+                .map(|(version, artifact, _)| (version.parse().unwrap(), artifact.to_string()))
+                .collect(),
+            latest_release: None,
+        }
+    }
+
+    #[test]
+    fn diff_release_lists() {
+        let old = list(&[
+            ("0.8.7", "solc-v0.8.7", 1),
+            ("0.8.8", "solc-v0.8.8", 2),
+            ("0.8.9", "solc-v0.8.9", 3),
+            ("0.8.10", "solc-v0.8.10", 4),
+        ]);
+        let new = list(&[
+            ("0.8.8", "solc-v0.8.8", 2),
+            ("0.8.9", "solc-v0.8.9", 9),
+            ("0.8.10", "solc-v0.8.10+commit.fc410830", 4),
+            ("0.8.11", "solc-v0.8.11", 5),
+        ]);
+        assert!(old.diff(&old).is_empty());
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, [Version::new(0, 8, 11)]);
+        assert_eq!(diff.removed, [Version::new(0, 8, 7)]);
+        assert_eq!(
+            diff.changed
+                .iter()
+                .map(|change| (
+                    change.version.to_string(),
+                    change.artifact_changed(),
+                    change.checksum_changed()
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("0.8.9".to_string(), false, true),
+                ("0.8.10".to_string(), true, false)
+            ]
+        );
+        assert_eq!(
+            diff.checksum_changes()
+                .map(|change| &change.version)
+                .collect::<Vec<_>>(),
+            [&Version::new(0, 8, 9)]
+        );
+    }
+}
@@ -0,0 +1,963 @@
+//! Minimal ELF reader and writer, used to inspect how solc binaries are linked and to patch their
+//! interpreter for NixOS.
+//!
+//! Only the parts of the format needed to read `PT_INTERP` and `DT_NEEDED`, and to rewrite
+//! `PT_INTERP`, are supported, for both 32 and 64-bit, little and big endian files.
+
+use crate::{SvmError, platform};
+use std::{fmt, fs, path::Path};
+
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+
+const ELFCLASS32: u8 = 1;
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const ELFDATA2MSB: u8 = 2;
+
+const PT_NULL: u32 = 0;
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PT_INTERP: u32 = 3;
+const PT_NOTE: u32 = 4;
+
+const PF_R: u32 = 4;
+
+const DT_NULL: u64 = 0;
+const DT_NEEDED: u64 = 1;
+const DT_STRTAB: u64 = 5;
+
+/// Alignment of the segment added when relocating the interpreter.
+///
+/// This is the largest page size used by Linux on the supported architectures (64K on aarch64).
+const RELOCATED_SEGMENT_ALIGN: u64 = 0x10000;
+
+/// A parsed ELF file header.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Elf {
+    is_64: bool,
+    is_le: bool,
+    pub(crate) machine: u16,
+    phoff: u64,
+    phentsize: u16,
+    phnum: u16,
+    shoff: u64,
+    shentsize: u16,
+    shnum: u16,
+}
+
+/// A program header, with the fields widened to their 64-bit representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ProgramHeader {
+    pub(crate) p_type: u32,
+    pub(crate) flags: u32,
+    pub(crate) offset: u64,
+    pub(crate) vaddr: u64,
+    pub(crate) paddr: u64,
+    pub(crate) filesz: u64,
+    pub(crate) memsz: u64,
+    pub(crate) align: u64,
+}
+
+impl Elf {
+    /// Parses the ELF file header.
+    pub(crate) fn parse(data: &[u8]) -> Result<Self, SvmError> {
+        if data.get(..4) != Some(ELF_MAGIC) {
+            return Err(invalid("missing ELF magic"));
+        }
+        let is_64 = match data.get(4) {
+            Some(&ELFCLASS32) => false,
+            Some(&ELFCLASS64) => true,
+            _ => return Err(invalid("unknown ELF class")),
+        };
+        let is_le = match data.get(5) {
+            Some(&ELFDATA2LSB) => true,
+            Some(&ELFDATA2MSB) => false,
+            _ => return Err(invalid("unknown ELF data encoding")),
+        };
+
+        let mut elf = Self {
+            is_64,
+            is_le,
+            machine: 0,
+            phoff: 0,
+            phentsize: 0,
+            phnum: 0,
+            shoff: 0,
+            shentsize: 0,
+            shnum: 0,
+        };
+        let (phoff, shoff, rest) = if is_64 { (32, 40, 54) } else { (28, 32, 42) };
+        elf.machine = elf.read_u16(data, 18)?;
+        elf.phoff = elf.read_addr(data, phoff)?;
+        elf.shoff = elf.read_addr(data, shoff)?;
+        elf.phentsize = elf.read_u16(data, rest)?;
+        elf.phnum = elf.read_u16(data, rest + 2)?;
+        elf.shentsize = elf.read_u16(data, rest + 4)?;
+        elf.shnum = elf.read_u16(data, rest + 6)?;
+
+        let expected = if is_64 { 56 } else { 32 };
+        if elf.phnum > 0 && elf.phentsize != expected {
+            return Err(invalid("unexpected program header size"));
+        }
+        Ok(elf)
+    }
+
+    /// Reads all program headers.
+    pub(crate) fn program_headers(&self, data: &[u8]) -> Result<Vec<ProgramHeader>, SvmError> {
+        (0..self.phnum as usize)
+            .map(|i| self.read_program_header(data, self.program_header_offset(i)?))
+            .collect()
+    }
+
+    /// Returns the file offset of the `i`th program header, checking that the whole entry is
+    /// addressable.
+    fn program_header_offset(&self, i: usize) -> Result<usize, SvmError> {
+        entry_offset(self.phoff, self.phentsize, i, self.phentsize.into())
+            .ok_or_else(|| invalid("program header table out of bounds"))
+    }
+
+    fn read_program_header(&self, data: &[u8], at: usize) -> Result<ProgramHeader, SvmError> {
+        Ok(if self.is_64 {
+            ProgramHeader {
+                p_type: self.read_u32(data, at)?,
+                flags: self.read_u32(data, at + 4)?,
+                offset: self.read_u64(data, at + 8)?,
+                vaddr: self.read_u64(data, at + 16)?,
+                paddr: self.read_u64(data, at + 24)?,
+                filesz: self.read_u64(data, at + 32)?,
+                memsz: self.read_u64(data, at + 40)?,
+                align: self.read_u64(data, at + 48)?,
+            }
+        } else {
+            ProgramHeader {
+                p_type: self.read_u32(data, at)?,
+                offset: self.read_u32(data, at + 4)?.into(),
+                vaddr: self.read_u32(data, at + 8)?.into(),
+                paddr: self.read_u32(data, at + 12)?.into(),
+                filesz: self.read_u32(data, at + 16)?.into(),
+                memsz: self.read_u32(data, at + 20)?.into(),
+                flags: self.read_u32(data, at + 24)?,
+                align: self.read_u32(data, at + 28)?.into(),
+            }
+        })
+    }
+
+    /// Overwrites the program header table with `phdrs`, which must have `phnum` entries.
+    fn write_program_headers(
+        &self,
+        data: &mut [u8],
+        phdrs: &[ProgramHeader],
+    ) -> Result<(), SvmError> {
+        debug_assert_eq!(phdrs.len(), self.phnum as usize);
+        for (i, ph) in phdrs.iter().enumerate() {
+            let at = self.program_header_offset(i)?;
+            if self.is_64 {
+                self.write_u32(data, at, ph.p_type)?;
+                self.write_u32(data, at + 4, ph.flags)?;
+                self.write_u64(data, at + 8, ph.offset)?;
+                self.write_u64(data, at + 16, ph.vaddr)?;
+                self.write_u64(data, at + 24, ph.paddr)?;
+                self.write_u64(data, at + 32, ph.filesz)?;
+                self.write_u64(data, at + 40, ph.memsz)?;
+                self.write_u64(data, at + 48, ph.align)?;
+            } else {
+                self.write_u32(data, at, ph.p_type)?;
+                self.write_u32(data, at + 4, narrow(ph.offset)?)?;
+                self.write_u32(data, at + 8, narrow(ph.vaddr)?)?;
+                self.write_u32(data, at + 12, narrow(ph.paddr)?)?;
+                self.write_u32(data, at + 16, narrow(ph.filesz)?)?;
+                self.write_u32(data, at + 20, narrow(ph.memsz)?)?;
+                self.write_u32(data, at + 24, ph.flags)?;
+                self.write_u32(data, at + 28, narrow(ph.align)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the program interpreter, or `None` if the binary is statically linked.
+    pub(crate) fn interpreter(&self, data: &[u8]) -> Result<Option<String>, SvmError> {
+        let Some(interp) = self
+            .program_headers(data)?
+            .into_iter()
+            .find(|ph| ph.p_type == PT_INTERP)
+        else {
+            return Ok(None);
+        };
+        let bytes = slice(data, interp.offset, interp.filesz)?;
+        let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+        String::from_utf8(bytes.to_vec())
+            .map(Some)
+            .map_err(|_| invalid("interpreter path is not valid UTF-8"))
+    }
+
+    /// Returns the shared libraries the binary depends on, as listed by `DT_NEEDED` entries.
+    fn needed(&self, data: &[u8], phdrs: &[ProgramHeader]) -> Result<Vec<String>, SvmError> {
+        let Some(dynamic) = phdrs.iter().find(|ph| ph.p_type == PT_DYNAMIC) else {
+            return Ok(vec![]);
+        };
+        let entry_size = if self.is_64 { 16 } else { 8 };
+        let dynamic = slice(data, dynamic.offset, dynamic.filesz)?;
+
+        let mut needed = vec![];
+        let mut strtab = None;
+        for entry in dynamic.chunks_exact(entry_size) {
+            let tag = self.read_addr(entry, 0)?;
+            let value = self.read_addr(entry, entry_size / 2)?;
+            match tag {
+                DT_NULL => break,
+                DT_NEEDED => needed.push(value),
+                DT_STRTAB => strtab = Some(value),
+                _ => {}
+            }
+        }
+        if needed.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // `DT_STRTAB` is a virtual address, map it back to a file offset.
+        let strtab = strtab.ok_or_else(|| invalid("missing dynamic string table"))?;
+        let strtab = phdrs
+            .iter()
+            .find(|ph| {
+                ph.p_type == PT_LOAD
+                    && ph.vaddr <= strtab
+                    && ph
+                        .vaddr
+                        .checked_add(ph.filesz)
+                        .is_some_and(|end| strtab < end)
+            })
+            .ok_or_else(|| invalid("dynamic string table is not mapped"))
+            .and_then(|ph| {
+                ph.offset
+                    .checked_add(strtab - ph.vaddr)
+                    .ok_or_else(|| invalid("dynamic string table out of bounds"))
+            })?;
+        needed
+            .into_iter()
+            .map(|name| {
+                let start = strtab
+                    .checked_add(name)
+                    .and_then(|start| usize::try_from(start).ok())
+                    .ok_or_else(|| invalid("unexpected end of file"))?;
+                let bytes = data
+                    .get(start..)
+                    .ok_or_else(|| invalid("unexpected end of file"))?;
+                let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+                Ok(String::from_utf8_lossy(bytes).into_owned())
+            })
+            .collect()
+    }
+
+    /// Updates the `.interp` section header, if any, after the `PT_INTERP` segment was moved from
+    /// `old`.
+    ///
+    /// This is not needed to run the binary but keeps the file consistent for other tools.
+    fn update_interp_section(
+        &self,
+        data: &mut [u8],
+        old: &ProgramHeader,
+        new: &ProgramHeader,
+    ) -> Result<(), SvmError> {
+        let (addr, offset, size) = if self.is_64 {
+            (16, 24, 32)
+        } else {
+            (12, 16, 20)
+        };
+        let len = size + if self.is_64 { 8 } else { 4 };
+        for i in 0..self.shnum as usize {
+            let at = entry_offset(self.shoff, self.shentsize, i, len)
+                .ok_or_else(|| invalid("section header table out of bounds"))?;
+            if self.read_addr(data, at + offset)? != old.offset
+                || self.read_addr(data, at + addr)? != old.vaddr
+            {
+                continue;
+            }
+            self.write_addr(data, at + addr, new.vaddr)?;
+            self.write_addr(data, at + offset, new.offset)?;
+            self.write_addr(data, at + size, new.filesz)?;
+            break;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_u16(&self, data: &[u8], at: usize) -> Result<u16, SvmError> {
+        let bytes = fixed(data, at)?;
+        Ok(if self.is_le {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    }
+
+    pub(crate) fn read_u32(&self, data: &[u8], at: usize) -> Result<u32, SvmError> {
+        let bytes = fixed(data, at)?;
+        Ok(if self.is_le {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    pub(crate) fn read_u64(&self, data: &[u8], at: usize) -> Result<u64, SvmError> {
+        let bytes = fixed(data, at)?;
+        Ok(if self.is_le {
+            u64::from_le_bytes(bytes)
+        } else {
+            u64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Reads an address or offset, whose size depends on the ELF class.
+    pub(crate) fn read_addr(&self, data: &[u8], at: usize) -> Result<u64, SvmError> {
+        if self.is_64 {
+            self.read_u64(data, at)
+        } else {
+            self.read_u32(data, at).map(Into::into)
+        }
+    }
+
+    #[cfg(test)]
+    fn write_u16(&self, data: &mut [u8], at: usize, value: u16) -> Result<(), SvmError> {
+        let bytes = if self.is_le {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        };
+        write(data, at, &bytes)
+    }
+
+    fn write_u32(&self, data: &mut [u8], at: usize, value: u32) -> Result<(), SvmError> {
+        let bytes = if self.is_le {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        };
+        write(data, at, &bytes)
+    }
+
+    fn write_u64(&self, data: &mut [u8], at: usize, value: u64) -> Result<(), SvmError> {
+        let bytes = if self.is_le {
+            value.to_le_bytes()
+        } else {
+            value.to_be_bytes()
+        };
+        write(data, at, &bytes)
+    }
+
+    fn write_addr(&self, data: &mut [u8], at: usize, value: u64) -> Result<(), SvmError> {
+        if self.is_64 {
+            self.write_u64(data, at, value)
+        } else {
+            self.write_u32(data, at, narrow(value)?)
+        }
+    }
+}
+
+/// How an ELF binary is linked, as read from its headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkInfo {
+    /// Path of the dynamic loader requested by the binary, or `None` if it is statically linked.
+    pub interpreter: Option<String>,
+    /// Shared libraries the binary depends on.
+    pub needed: Vec<String>,
+}
+
+impl LinkInfo {
+    /// Reads the link information of an ELF binary.
+    pub fn parse(data: &[u8]) -> Result<Self, SvmError> {
+        let elf = Elf::parse(data)?;
+        let phdrs = elf.program_headers(data)?;
+        Ok(Self {
+            interpreter: elf.interpreter(data)?,
+            needed: elf.needed(data, &phdrs)?,
+        })
+    }
+
+    /// Reads the link information of the ELF binary at `path`.
+    pub fn from_path(path: &Path) -> Result<Self, SvmError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Returns `true` if the binary does not need a dynamic loader.
+    pub const fn is_static(&self) -> bool {
+        self.interpreter.is_none()
+    }
+
+    /// Returns the C library the binary was linked against, if it is dynamically linked.
+    pub fn libc(&self) -> Option<Libc> {
+        let interpreter = self.interpreter.as_deref()?;
+        let name = Path::new(interpreter).file_name()?.to_str()?;
+        if name.starts_with("ld-musl") || self.needed.iter().any(|lib| lib.starts_with("libc.musl"))
+        {
+            Some(Libc::Musl)
+        } else if name.starts_with("ld-linux") || self.needed.iter().any(|lib| lib == "libc.so.6") {
+            Some(Libc::Glibc)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the dynamic loader requested by the binary does not exist on this host.
+    ///
+    /// This is the case for most dynamically linked binaries on NixOS, which must then be patched
+    /// to use the Nix store's loader.
+    pub fn is_interpreter_missing(&self) -> bool {
+        self.interpreter
+            .as_deref()
+            .is_some_and(|interpreter| !Path::new(interpreter).exists())
+    }
+
+    /// Checks whether the binary can run on this host, returning the reason if it can't.
+    pub fn host_incompatibility(&self) -> Option<HostIncompatibility> {
+        if !self.is_interpreter_missing() {
+            return None;
+        }
+        if self.libc() == Some(Libc::Glibc) && platform::host_libc() == Some(Libc::Musl) {
+            return Some(HostIncompatibility::GlibcOnMusl);
+        }
+        self.interpreter
+            .clone()
+            .map(HostIncompatibility::MissingInterpreter)
+    }
+}
+
+/// C library implementations that solc binaries are linked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Libc {
+    /// The GNU C library.
+    Glibc,
+    /// The musl C library, used by e.g. Alpine Linux.
+    Musl,
+}
+
+/// Reason why a binary can't run on this host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HostIncompatibility {
+    /// The binary is linked against glibc, but the host uses musl.
+    GlibcOnMusl,
+    /// The dynamic loader requested by the binary does not exist.
+    MissingInterpreter(String),
+}
+
+impl fmt::Display for HostIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GlibcOnMusl => f.write_str(
+                "binary is linked against glibc, but this host uses musl; install a glibc \
+                 compatibility layer such as `gcompat`",
+            ),
+            Self::MissingInterpreter(interpreter) => {
+                write!(
+                    f,
+                    "dynamic loader {interpreter} does not exist on this host"
+                )
+            }
+        }
+    }
+}
+
+/// Returns the path of the program interpreter of an ELF binary, or `None` if it is statically
+/// linked.
+pub(crate) fn interpreter(data: &[u8]) -> Result<Option<String>, SvmError> {
+    Elf::parse(data)?.interpreter(data)
+}
+
+/// Sets the program interpreter of an ELF binary, similar to `patchelf --set-interpreter`.
+///
+/// If the new path fits in the existing `PT_INTERP` segment it is overwritten in place. Otherwise
+/// the path is appended to the file and mapped by a new `PT_LOAD` segment, which takes the program
+/// header slot of a `PT_NOTE` (or `PT_NULL`) entry so the program header table does not move.
+pub(crate) fn set_interpreter(data: &mut Vec<u8>, interpreter: &str) -> Result<(), SvmError> {
+    let elf = Elf::parse(data)?;
+    let mut phdrs = elf.program_headers(data)?;
+    let interp_idx = phdrs
+        .iter()
+        .position(|ph| ph.p_type == PT_INTERP)
+        .ok_or_else(|| invalid("binary is statically linked and has no interpreter"))?;
+    let old = phdrs[interp_idx];
+
+    let mut path = interpreter.as_bytes().to_vec();
+    path.push(0);
+    let len = path.len() as u64;
+
+    if len <= old.filesz {
+        let start = old.offset as usize;
+        slice(data, old.offset, old.filesz)?;
+        data[start..start + old.filesz as usize].fill(0);
+        data[start..start + path.len()].copy_from_slice(&path);
+        let new = ProgramHeader {
+            filesz: len,
+            memsz: len,
+            ..old
+        };
+        phdrs[interp_idx] = new;
+        elf.write_program_headers(data, &phdrs)?;
+        return elf.update_interp_section(data, &old, &new);
+    }
+
+    // Reuse the last note segment, which is not needed to run the binary, or an unused entry.
+    let spare_idx = phdrs
+        .iter()
+        .rposition(|ph| ph.p_type == PT_NOTE)
+        .or_else(|| phdrs.iter().position(|ph| ph.p_type == PT_NULL))
+        .ok_or_else(|| invalid("no program header available to relocate the interpreter"))?;
+
+    let loads = phdrs.iter().filter(|ph| ph.p_type == PT_LOAD);
+    let align = loads
+        .clone()
+        .map(|ph| ph.align)
+        .fold(RELOCATED_SEGMENT_ALIGN, u64::max);
+    let end = loads
+        .map(|ph| {
+            ph.vaddr
+                .checked_add(ph.memsz)
+                .ok_or_else(|| invalid("segment out of bounds"))
+        })
+        .try_fold(0, |end, seg_end| seg_end.map(|seg_end| end.max(seg_end)))?;
+
+    // The kernel requires `offset % align == vaddr % align`, so place the segment after every
+    // other one in memory without having to pad the file.
+    let offset = data.len() as u64;
+    let vaddr = end
+        .checked_next_multiple_of(align)
+        .and_then(|end| end.checked_add(offset % align))
+        .ok_or_else(|| invalid("segment out of bounds"))?;
+    data.extend_from_slice(&path);
+
+    let new = ProgramHeader {
+        offset,
+        vaddr,
+        paddr: vaddr,
+        filesz: len,
+        memsz: len,
+        ..old
+    };
+    let load = ProgramHeader {
+        p_type: PT_LOAD,
+        flags: PF_R,
+        offset,
+        vaddr,
+        paddr: vaddr,
+        filesz: len,
+        memsz: len,
+        align,
+    };
+    phdrs[interp_idx] = new;
+
+    // `PT_LOAD` entries must be sorted by address, so the new one goes after the last of them.
+    phdrs.remove(spare_idx);
+    let last_load = phdrs
+        .iter()
+        .rposition(|ph| ph.p_type == PT_LOAD)
+        .map_or(phdrs.len(), |i| i + 1);
+    phdrs.insert(last_load, load);
+
+    elf.write_program_headers(data, &phdrs)?;
+    elf.update_interp_section(data, &old, &new)
+}
+
+fn invalid(msg: &str) -> SvmError {
+    SvmError::InvalidBinary(msg.to_string())
+}
+
+/// Returns the file offset of the `i`th entry of the header table at `table`, checking that `len`
+/// bytes from it are addressable.
+fn entry_offset(table: u64, entsize: u16, i: usize, len: usize) -> Option<usize> {
+    let at = usize::try_from(table)
+        .ok()?
+        .checked_add(i.checked_mul(entsize.into())?)?;
+    at.checked_add(len).map(|_| at)
+}
+
+fn narrow(value: u64) -> Result<u32, SvmError> {
+    value
+        .try_into()
+        .map_err(|_| invalid("value does not fit in a 32-bit ELF file"))
+}
+
+pub(crate) fn slice(data: &[u8], offset: u64, len: u64) -> Result<&[u8], SvmError> {
+    usize::try_from(offset)
+        .ok()
+        .zip(usize::try_from(len).ok())
+        .and_then(|(offset, len)| data.get(offset..offset.checked_add(len)?))
+        .ok_or_else(|| invalid("unexpected end of file"))
+}
+
+fn fixed<const N: usize>(data: &[u8], at: usize) -> Result<[u8; N], SvmError> {
+    at.checked_add(N)
+        .and_then(|end| data.get(at..end))
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| invalid("unexpected end of file"))
+}
+
+fn write(data: &mut [u8], at: usize, bytes: &[u8]) -> Result<(), SvmError> {
+    at.checked_add(bytes.len())
+        .and_then(|end| data.get_mut(at..end))
+        .ok_or_else(|| invalid("unexpected end of file"))?
+        .copy_from_slice(bytes);
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const INTERP: &str = "/lib64/ld-linux-x86-64.so.2";
+    const NIX_INTERP: &str =
+        "/nix/store/7n0mbqydcipkpbxm24fab066lxk68aqk-glibc-2.40-66/lib/ld-linux-x86-64.so.2";
+
+    /// Describes a minimal synthetic ELF executable for tests.
+    pub(crate) struct Fixture {
+        pub(crate) is_64: bool,
+        pub(crate) is_le: bool,
+        pub(crate) machine: u16,
+        pub(crate) interp: Option<&'static str>,
+        pub(crate) needed: Vec<&'static str>,
+    }
+
+    impl Fixture {
+        /// A dynamically linked x86-64 glibc executable.
+        pub(crate) fn new() -> Self {
+            Self {
+                is_64: true,
+                is_le: true,
+                machine: 62,
+                interp: Some(INTERP),
+                needed: vec!["libc.so.6"],
+            }
+        }
+
+        pub(crate) fn class(self, is_64: bool, is_le: bool) -> Self {
+            Self {
+                is_64,
+                is_le,
+                ..self
+            }
+        }
+
+        pub(crate) fn machine(self, machine: u16) -> Self {
+            Self { machine, ..self }
+        }
+
+        pub(crate) fn interp(self, interp: Option<&'static str>) -> Self {
+            Self { interp, ..self }
+        }
+
+        pub(crate) fn needed(self, needed: Vec<&'static str>) -> Self {
+            Self { needed, ..self }
+        }
+
+        /// Builds an executable with `PT_PHDR`, `PT_INTERP`, two `PT_LOAD`, `PT_DYNAMIC` and
+        /// `PT_NOTE` segments, plus an `.interp` section header.
+        pub(crate) fn build(&self) -> Vec<u8> {
+            let Self { is_64, is_le, .. } = *self;
+            let (ehsize, phentsize, shentsize) = if is_64 { (64, 56, 64) } else { (52, 32, 40) };
+            let base = 0x400000;
+            let phnum = 6u64;
+            let phoff = ehsize;
+
+            let interp_off = phoff + phnum * phentsize;
+            let interp = self.interp.map(|i| format!("{i}\0")).unwrap_or_default();
+            let interp_len = interp.len() as u64;
+
+            let dynstr_off = interp_off + interp_len;
+            let mut dynstr = vec![0u8];
+            let mut dynamic = vec![];
+            for lib in &self.needed {
+                dynamic.push((DT_NEEDED, dynstr.len() as u64));
+                dynstr.extend_from_slice(lib.as_bytes());
+                dynstr.push(0);
+            }
+            dynamic.push((DT_STRTAB, base + dynstr_off));
+            dynamic.push((DT_NULL, 0));
+            let dyn_off = (dynstr_off + dynstr.len() as u64).next_multiple_of(8);
+            let dyn_len = dynamic.len() as u64 * if is_64 { 16 } else { 8 };
+
+            let shoff = (dyn_off + dyn_len).next_multiple_of(8);
+            let shnum = 2u64;
+            let len = shoff + shnum * shentsize;
+
+            let mut data = vec![0u8; len as usize];
+            data[..4].copy_from_slice(ELF_MAGIC);
+            data[4] = if is_64 { ELFCLASS64 } else { ELFCLASS32 };
+            data[5] = if is_le { ELFDATA2LSB } else { ELFDATA2MSB };
+            data[6] = 1;
+            let elf = Elf {
+                is_64,
+                is_le,
+                machine: self.machine,
+                phoff,
+                phentsize: phentsize as u16,
+                phnum: phnum as u16,
+                shoff,
+                shentsize: shentsize as u16,
+                shnum: shnum as u16,
+            };
+            let (phoff_at, rest) = if is_64 { (32, 52) } else { (28, 40) };
+            elf.write_u16(&mut data, 16, 2).unwrap();
+            elf.write_u16(&mut data, 18, self.machine).unwrap();
+            elf.write_addr(&mut data, phoff_at, phoff).unwrap();
+            elf.write_addr(&mut data, phoff_at + if is_64 { 8 } else { 4 }, shoff)
+                .unwrap();
+            for (i, v) in [ehsize, phentsize, phnum, shentsize, shnum]
+                .into_iter()
+                .enumerate()
+            {
+                elf.write_u16(&mut data, rest + 2 * i, v as u16).unwrap();
+            }
+
+            let ph = |p_type, offset, filesz, align| ProgramHeader {
+                p_type,
+                flags: PF_R,
+                offset,
+                vaddr: base + offset,
+                paddr: base + offset,
+                filesz,
+                memsz: filesz,
+                align,
+            };
+            let null = ph(PT_NULL, 0, 0, 0);
+            let mut phdrs = vec![
+                ph(6, phoff, phnum * phentsize, 8),
+                if self.interp.is_some() {
+                    ph(PT_INTERP, interp_off, interp_len, 1)
+                } else {
+                    null
+                },
+                ph(PT_LOAD, 0, len, 0x1000),
+                ph(PT_LOAD, 0, 0, 0x1000),
+                if self.interp.is_some() {
+                    ph(PT_DYNAMIC, dyn_off, dyn_len, 8)
+                } else {
+                    null
+                },
+                ph(PT_NOTE, interp_off, 0, 4),
+            ];
+            phdrs[3].vaddr = base + 0x10000;
+            phdrs[3].memsz = 0x2000;
+            elf.write_program_headers(&mut data, &phdrs).unwrap();
+
+            data[interp_off as usize..][..interp.len()].copy_from_slice(interp.as_bytes());
+            data[dynstr_off as usize..][..dynstr.len()].copy_from_slice(&dynstr);
+            for (i, (tag, value)) in dynamic.into_iter().enumerate() {
+                let at = dyn_off as usize + i * if is_64 { 16 } else { 8 };
+                elf.write_addr(&mut data, at, tag).unwrap();
+                elf.write_addr(&mut data, at + if is_64 { 8 } else { 4 }, value)
+                    .unwrap();
+            }
+
+            // Section 0 is the null section, section 1 is `.interp`.
+            let sh = shoff as usize + shentsize as usize;
+            let (addr, offset, size) = if is_64 { (16, 24, 32) } else { (12, 16, 20) };
+            elf.write_u32(&mut data, sh + 4, 1).unwrap();
+            elf.write_addr(&mut data, sh + addr, base + interp_off)
+                .unwrap();
+            elf.write_addr(&mut data, sh + offset, interp_off).unwrap();
+            elf.write_addr(&mut data, sh + size, interp_len).unwrap();
+            data
+        }
+    }
+
+    fn fixture(is_64: bool, is_le: bool, interp: Option<&'static str>) -> Vec<u8> {
+        Fixture::new().class(is_64, is_le).interp(interp).build()
+    }
+
+    fn interp_section(data: &[u8]) -> (u64, u64, u64) {
+        let elf = Elf::parse(data).unwrap();
+        let sh = elf.shoff as usize + elf.shentsize as usize;
+        let (addr, offset, size) = if elf.is_64 {
+            (16, 24, 32)
+        } else {
+            (12, 16, 20)
+        };
+        (
+            elf.read_addr(data, sh + addr).unwrap(),
+            elf.read_addr(data, sh + offset).unwrap(),
+            elf.read_addr(data, sh + size).unwrap(),
+        )
+    }
+
+    #[test]
+    fn read_interpreter() {
+        for (is_64, is_le) in [(true, true), (true, false), (false, true), (false, false)] {
+            let data = fixture(is_64, is_le, Some(INTERP));
+            assert_eq!(interpreter(&data).unwrap().as_deref(), Some(INTERP));
+            let data = fixture(is_64, is_le, None);
+            assert_eq!(interpreter(&data).unwrap(), None);
+        }
+        assert!(interpreter(b"#!/bin/sh\n").is_err());
+        assert!(interpreter(&fixture(true, true, Some(INTERP))[..80]).is_err());
+    }
+
+    #[test]
+    fn link_info() {
+        for (is_64, is_le) in [(true, true), (true, false), (false, true), (false, false)] {
+            let data = Fixture::new()
+                .class(is_64, is_le)
+                .needed(vec!["libstdc++.so.6", "libc.so.6"])
+                .build();
+            let info = LinkInfo::parse(&data).unwrap();
+            assert_eq!(info.interpreter.as_deref(), Some(INTERP));
+            assert_eq!(info.needed, ["libstdc++.so.6", "libc.so.6"]);
+            assert_eq!(info.libc(), Some(Libc::Glibc));
+            assert!(!info.is_static());
+        }
+
+        let info = LinkInfo::parse(&Fixture::new().interp(None).build()).unwrap();
+        assert!(info.is_static());
+        assert!(info.needed.is_empty());
+        assert_eq!(info.libc(), None);
+        assert_eq!(info.host_incompatibility(), None);
+
+        let musl = Fixture::new()
+            .interp(Some("/lib/ld-musl-x86_64.so.1"))
+            .needed(vec!["libc.musl-x86_64.so.1"])
+            .build();
+        assert_eq!(LinkInfo::parse(&musl).unwrap().libc(), Some(Libc::Musl));
+    }
+
+    #[test]
+    fn missing_interpreter() {
+        let info = LinkInfo::parse(&fixture(true, true, Some("/does/not/exist/ld.so"))).unwrap();
+        assert!(info.is_interpreter_missing());
+        assert_eq!(
+            info.host_incompatibility(),
+            Some(HostIncompatibility::MissingInterpreter(
+                "/does/not/exist/ld.so".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn set_interpreter_in_place() {
+        let mut data = fixture(true, true, Some(INTERP));
+        let len = data.len();
+        set_interpreter(&mut data, "/lib/ld.so").unwrap();
+        assert_eq!(data.len(), len);
+        assert_eq!(interpreter(&data).unwrap().as_deref(), Some("/lib/ld.so"));
+        assert_eq!(interp_section(&data).2, "/lib/ld.so\0".len() as u64);
+    }
+
+    #[test]
+    fn set_interpreter_relocated() {
+        for (is_64, is_le) in [(true, true), (true, false), (false, true), (false, false)] {
+            let mut data = fixture(is_64, is_le, Some(INTERP));
+            let before = Elf::parse(&data).unwrap().program_headers(&data).unwrap();
+            set_interpreter(&mut data, NIX_INTERP).unwrap();
+            assert_eq!(interpreter(&data).unwrap().as_deref(), Some(NIX_INTERP));
+
+            let phdrs = Elf::parse(&data).unwrap().program_headers(&data).unwrap();
+            assert_eq!(phdrs.len(), before.len());
+            assert!(phdrs.iter().all(|ph| ph.p_type != PT_NOTE));
+
+            // The interpreter is mapped by the last `PT_LOAD`, above all other segments.
+            let interp = phdrs.iter().find(|ph| ph.p_type == PT_INTERP).unwrap();
+            let loads = phdrs
+                .iter()
+                .filter(|ph| ph.p_type == PT_LOAD)
+                .collect::<Vec<_>>();
+            assert!(loads.is_sorted_by_key(|ph| ph.vaddr));
+            let load = loads.last().unwrap();
+            assert_eq!((load.offset, load.vaddr), (interp.offset, interp.vaddr));
+            assert_eq!(load.offset % load.align, load.vaddr % load.align);
+            assert_eq!(
+                interp_section(&data),
+                (interp.vaddr, interp.offset, interp.filesz)
+            );
+        }
+    }
+
+    #[test]
+    fn overflowing_headers() {
+        let fixture = fixture(true, true, Some(INTERP));
+
+        // A program header table past the end of the address space.
+        let mut data = fixture.clone();
+        data[32..40].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        assert!(matches!(
+            interpreter(&data),
+            Err(SvmError::InvalidBinary(_))
+        ));
+
+        // A section header table past the end of the address space.
+        let mut data = fixture.clone();
+        data[40..48].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        assert!(matches!(
+            set_interpreter(&mut data, "/lib/ld.so"),
+            Err(SvmError::InvalidBinary(_))
+        ));
+
+        // Segments wrapping around the address space.
+        let mut data = fixture.clone();
+        let elf = Elf::parse(&data).unwrap();
+        let mut phdrs = elf.program_headers(&data).unwrap();
+        for ph in phdrs.iter_mut().filter(|ph| ph.p_type == PT_LOAD) {
+            ph.vaddr = u64::MAX;
+            ph.filesz = u64::MAX;
+            ph.memsz = u64::MAX;
+        }
+        elf.write_program_headers(&mut data, &phdrs).unwrap();
+        assert!(matches!(
+            LinkInfo::parse(&data),
+            Err(SvmError::InvalidBinary(_))
+        ));
+        assert!(matches!(
+            set_interpreter(&mut data, NIX_INTERP),
+            Err(SvmError::InvalidBinary(_))
+        ));
+
+        // Relocating the interpreter past the last segment would wrap around the address space.
+        let mut data = fixture;
+        let mut phdrs = elf.program_headers(&data).unwrap();
+        for ph in phdrs.iter_mut().filter(|ph| ph.p_type == PT_LOAD) {
+            ph.vaddr = u64::MAX - 0x10;
+            ph.memsz = 1;
+        }
+        elf.write_program_headers(&mut data, &phdrs).unwrap();
+        assert!(matches!(
+            set_interpreter(&mut data, NIX_INTERP),
+            Err(SvmError::InvalidBinary(_))
+        ));
+    }
+
+    #[test]
+    fn set_interpreter_static() {
+        let mut data = fixture(true, true, None);
+        assert!(set_interpreter(&mut data, NIX_INTERP).is_err());
+    }
+
+    /// Relocates the interpreter of a real dynamically linked binary (this test binary) to a
+    /// longer path and checks that it still runs.
+    #[test]
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    fn patched_binary_runs() {
+        let exe = std::env::current_exe().unwrap();
+        let mut data = std::fs::read(&exe).unwrap();
+        let interp = interpreter(&data).unwrap().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let long_dir = dir.path().join("a".repeat(100));
+        std::fs::create_dir(&long_dir).unwrap();
+        let long_interp = long_dir.join("ld.so");
+        std::os::unix::fs::symlink(&interp, &long_interp).unwrap();
+
+        set_interpreter(&mut data, long_interp.to_str().unwrap()).unwrap();
+        let patched = dir.path().join("patched");
+        std::fs::write(&patched, &data).unwrap();
+        std::fs::set_permissions(
+            &patched,
+            std::os::unix::fs::PermissionsExt::from_mode(0o755),
+        )
+        .unwrap();
+
+        let output = std::process::Command::new(&patched)
+            .arg("--list")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(
+            interpreter(&std::fs::read(&patched).unwrap()).unwrap(),
+            long_interp.to_str().map(ToString::to_string)
+        );
+    }
+}
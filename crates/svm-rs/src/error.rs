@@ -12,6 +12,8 @@ pub enum SvmError {
     UnknownVersion(Version),
     #[error("Unsupported version {0} for platform {1}")]
     UnsupportedVersion(String, String),
+    #[error("Checksum of solc version {0} not available")]
+    ChecksumNotAvailable(String),
     #[error("Version {0} not installed")]
     VersionNotInstalled(String),
     #[error("Checksum mismatch for version {version}: expected: {expected}, actual: {actual}")]
@@ -20,12 +22,54 @@ pub enum SvmError {
         expected: String,
         actual: String,
     },
-    #[error("Install step for solc version {0} timed out after {1} seconds")]
+    #[error(
+        "Keccak-256 checksum mismatch for version {version}: expected: {expected}, actual: {actual}"
+    )]
+    Keccak256Mismatch {
+        version: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Solc {version} artifact is built for {actual}, which cannot run on {platform}")]
+    PlatformMismatch {
+        version: String,
+        platform: String,
+        actual: String,
+    },
+    #[error(
+        "Solc {version} for {platform} is a community build by {provider}, but only official builds are allowed"
+    )]
+    UntrustedBuild {
+        version: String,
+        platform: String,
+        provider: String,
+    },
+    #[error("Solc {version} was installed but failed verification: {reason}")]
+    InstallVerificationFailed { version: String, reason: String },
+    #[error("Solc {0} timed out after {1} seconds")]
     Timeout(String, u64),
-    #[error("Unable to patch solc binary for nixos. stdout: {0}. stderr: {1}")]
+    #[error("Solc {0} exceeded its memory limit of {1} bytes")]
+    MemoryLimitExceeded(String, u64),
+    #[error("Solc {0} exceeded its output limit of {1} bytes")]
+    OutputLimitExceeded(String, usize),
+    #[error("Unable to patch solc binary {0} for nixos: {1}")]
     CouldNotPatchForNixOs(String, String),
     #[error("Unable to add nix gcroot for solc runtime dependencies. stdout: {0}. stderr: {1}")]
     CouldNotAddNixGcRoot(String, String),
+    #[error("Invalid release list public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Could not verify the signature of {url}: {reason}")]
+    SignatureVerificationFailed { url: String, reason: String },
+    #[error("Release list {url} is invalid: {issues}")]
+    InvalidReleaseList { url: String, issues: String },
+    #[error("Unknown EVM version: {0}")]
+    UnknownEvmVersion(String),
+    #[error("Could not probe {path}: {reason}")]
+    ProbeFailed { path: String, reason: String },
+    #[error("Could not compile with {path}: {reason}")]
+    CompilationFailed { path: String, reason: String },
+    #[error("Invalid executable: {0}")]
+    InvalidBinary(String),
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
@@ -35,9 +79,13 @@ pub enum SvmError {
     #[error(transparent)]
     SemverError(#[from] semver::Error),
     #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error(transparent)]
     UrlError(#[from] url::ParseError),
     #[error("Received unsuccessful response with code {1} for {0}")]
     UnsuccessfulResponse(Url, StatusCode),
+    #[error("All mirrors failed: {0}")]
+    AllMirrorsFailed(String),
     #[cfg(target_os = "windows")]
     #[error(transparent)]
     ZipError(#[from] zip::result::ZipError),
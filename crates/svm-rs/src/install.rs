@@ -1,130 +1,481 @@
 use crate::{
-    SvmError, all_releases, data_dir, platform, releases::artifact_url, setup_data_dir,
-    setup_version, version_binary,
+    Artifact, BinaryInfo, LinkInfo, Platform, Release, Source, SvmError, Trust, data_dir, elf,
+    mirrors::{self, MirrorOverrides},
+    paths::{gc_root_path, gc_roots_dir},
+    platform,
+    releases::releases_with_fallback,
+    remove_gc_root, setup_data_dir, setup_version, version_binary,
 };
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use std::{
     fs,
-    io::{ErrorKind, Write},
+    io::Write,
     path::{Path, PathBuf},
     process::Command,
     time::Duration,
 };
 use tempfile::NamedTempFile;
+use url::Url;
 
 #[cfg(target_family = "unix")]
 use std::{fs::Permissions, os::unix::fs::PermissionsExt};
 
+/// Environment variable with the path of the dynamic linker to use when patching solc binaries for
+/// NixOS.
+///
+/// If unset, the `NIX_LD` dynamic linker or the interpreter of the current process is used.
+pub const SVM_DYNAMIC_LINKER: &str = "SVM_DYNAMIC_LINKER";
+
+/// Environment variable with the IPFS gateway used to download artifacts by their content address
+/// when the primary host fails, e.g. `http://127.0.0.1:8080`.
+///
+/// Defaults to `https://ipfs.io`.
+pub const SVM_IPFS_GATEWAY: &str = "SVM_IPFS_GATEWAY";
+
+/// Environment variable with the Swarm gateway used to download artifacts by their `bzzr://`
+/// content address when the primary host fails.
+///
+/// Swarm is not used unless this is set.
+pub const SVM_SWARM_GATEWAY: &str = "SVM_SWARM_GATEWAY";
+
+const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io";
+
+/// Environment variable that, if set to `1` or `true`, refuses to install community builds.
+pub const SVM_OFFICIAL_ONLY: &str = "SVM_OFFICIAL_ONLY";
+
+/// Where the installed binary of a Solc version was downloaded from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Origin {
+    /// The URL the artifact was downloaded from, which may be a mirror or a content address
+    /// gateway.
+    pub url: Url,
+    /// The source providing the artifact.
+    pub source: Source,
+    /// The hash of the Solidity commit the artifact was built from, e.g. `e28d00a7`, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl Origin {
+    /// Returns the commit the artifact was built from, as recorded at install time or read from
+    /// the name of the downloaded artifact.
+    pub fn commit(&self) -> Option<&str> {
+        self.commit.as_deref().or_else(|| {
+            self.url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .and_then(crate::releases::artifact_commit)
+        })
+    }
+}
+
 /// The timeout to use for requests to the source (10 minutes).
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
 
-/// Version beyond which solc binaries are not fully static, hence need to be patched for NixOS.
-const NIXOS_MIN_PATCH_VERSION: Version = Version::new(0, 7, 6);
+/// Options for [`install_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct InstallOptions {
+    smoke_test: bool,
+    official_only: bool,
+    ipfs_gateway: Option<String>,
+    swarm_gateway: Option<String>,
+    platform: Option<Platform>,
+    root: Option<PathBuf>,
+    mirrors: MirrorOverrides,
+}
 
-/// Version beyond which solc binaries are fully static again, hence no patching is needed for NixOS.
-/// See <https://github.com/ethereum/solidity/releases/tag/v0.8.29>
-const NIXOS_MAX_PATCH_VERSION: Version = Version::new(0, 8, 28);
+impl InstallOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-/// Blocking version of [`install`]
-#[cfg(feature = "blocking")]
-pub fn blocking_install(version: &Version) -> Result<PathBuf, SvmError> {
-    setup_data_dir()?;
+    /// Runs the installed binary with `--version` and checks that it reports the requested version
+    /// and the commit of the downloaded artifact.
+    ///
+    /// If the check fails, the version is removed again and
+    /// [`SvmError::InstallVerificationFailed`] is returned. The check is skipped for binaries
+    /// installed for another [`platform`](Self::platform).
+    pub const fn smoke_test(mut self, smoke_test: bool) -> Self {
+        self.smoke_test = smoke_test;
+        self
+    }
 
-    let artifacts = crate::blocking_all_releases(platform::platform())?;
-    let artifact = artifacts
-        .get_artifact(version)
-        .ok_or_else(|| SvmError::UnknownVersion(version.clone()))?;
-    let download_url = artifact_url(platform::platform(), version, artifact.to_string().as_str())?;
+    /// Refuses to install community builds, see [`Trust`].
+    ///
+    /// Community builds are also refused if [`SVM_OFFICIAL_ONLY`] is set.
+    pub const fn official_only(mut self, official_only: bool) -> Self {
+        self.official_only = official_only;
+        self
+    }
 
-    let expected_checksum = artifacts
-        .get_checksum(version)
-        .unwrap_or_else(|| panic!("checksum not available: {:?}", version.to_string()));
+    /// Returns an error if the artifact isn't allowed by the trust policy.
+    fn ensure_trusted(&self, artifact: &Artifact) -> Result<(), SvmError> {
+        let official_only = self.official_only
+            || std::env::var(SVM_OFFICIAL_ONLY)
+                .is_ok_and(|value| matches!(value.trim(), "1" | "true"));
+        if official_only && artifact.trust() != Trust::Official {
+            return Err(SvmError::UntrustedBuild {
+                version: artifact.version.to_string(),
+                platform: artifact.platform.to_string(),
+                provider: artifact.source.provider().to_string(),
+            });
+        }
+        Ok(())
+    }
 
-    let res = reqwest::blocking::Client::builder()
-        .timeout(REQUEST_TIMEOUT)
-        .build()
-        .expect("reqwest::Client::new()")
-        .get(download_url.clone())
-        .send()?;
+    /// Sets the IPFS gateway to fall back to when the primary host fails, overriding
+    /// [`SVM_IPFS_GATEWAY`].
+    pub fn ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = Some(gateway.into());
+        self
+    }
+
+    /// Sets the Swarm gateway to fall back to when the primary host fails, overriding
+    /// [`SVM_SWARM_GATEWAY`].
+    pub fn swarm_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.swarm_gateway = Some(gateway.into());
+        self
+    }
+
+    /// Installs the binary for `platform` rather than for the host platform.
+    ///
+    /// The binary is installed into a separate tree holding one directory per platform, at
+    /// `{root}/{platform}/{version}/solc-{version}`, where `root` defaults to [`data_dir`]. Binaries
+    /// in these trees are never patched for NixOS and are not listed by
+    /// [`installed_versions`](crate::installed_versions).
+    pub const fn platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    /// Installs the binary into a separate tree under `root`, see [`platform`](Self::platform).
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Sets the base URLs of the mirrors to download the release lists and artifacts of `source`
+    /// from, overriding its [`mirrors_env`](Source::mirrors_env).
+    pub fn mirrors(
+        mut self,
+        source: Source,
+        mirrors: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let mirrors = mirrors
+            .into_iter()
+            .map(|mirror| mirror.into().trim_end_matches('/').to_string())
+            .collect();
+        self.mirrors.set(source, mirrors);
+        self
+    }
+
+    /// Returns where to install to.
+    fn target(&self) -> InstallTarget {
+        let platform = self.platform.unwrap_or_else(platform::platform);
+        let dir = (self.platform.is_some() || self.root.is_some()).then(|| {
+            self.root
+                .clone()
+                .unwrap_or_else(|| data_dir().to_path_buf())
+                .join(platform.to_string())
+        });
+        InstallTarget { platform, dir }
+    }
 
-    if !res.status().is_success() {
-        return Err(SvmError::UnsuccessfulResponse(download_url, res.status()));
+    /// Returns the URLs to download an artifact from through the configured gateways, given the
+    /// content addresses of its build, e.g. `dweb:/ipfs/Qm...` or `bzzr://...`.
+    fn content_urls(&self, urls: &[String]) -> Vec<Url> {
+        let ipfs_gateway = self
+            .ipfs_gateway
+            .clone()
+            .or_else(|| std::env::var(SVM_IPFS_GATEWAY).ok())
+            .unwrap_or_else(|| DEFAULT_IPFS_GATEWAY.to_string());
+        let swarm_gateway = self
+            .swarm_gateway
+            .clone()
+            .or_else(|| std::env::var(SVM_SWARM_GATEWAY).ok());
+        urls.iter()
+            .filter_map(|url| {
+                if let Some(cid) = url.strip_prefix("dweb:/ipfs/") {
+                    gateway_url(&ipfs_gateway, "ipfs", cid)
+                } else if let Some(hash) = url.strip_prefix("bzzr://") {
+                    gateway_url(swarm_gateway.as_deref()?, "bzz-raw:", hash)
+                } else {
+                    None
+                }
+            })
+            .collect()
     }
+}
+
+/// The platform and directory a version is installed for.
+#[derive(Debug)]
+struct InstallTarget {
+    platform: Platform,
+    /// The directory holding the installed versions of a separate tree, or `None` for the
+    /// [`data_dir`].
+    dir: Option<PathBuf>,
+}
 
-    let binbytes = res.bytes()?;
-    ensure_checksum(&binbytes, version, &expected_checksum)?;
+impl InstallTarget {
+    /// Creates the directory holding the installed versions.
+    fn setup(&self) -> Result<(), SvmError> {
+        match &self.dir {
+            Some(dir) => Ok(fs::create_dir_all(dir)?),
+            None => setup_data_dir(),
+        }
+    }
+
+    fn dir(&self) -> &Path {
+        self.dir.as_deref().unwrap_or_else(|| data_dir())
+    }
+
+    /// Returns the path to the installed binary of `version`.
+    fn solc_path(&self, version: &Version) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir
+                .join(version.to_string())
+                .join(format!("solc-{version}")),
+            None => version_binary(&version.to_string()),
+        }
+    }
+
+    fn setup_version(&self, version: &Version) -> Result<(), SvmError> {
+        match &self.dir {
+            Some(dir) => Ok(fs::create_dir_all(dir.join(version.to_string()))?),
+            None => setup_version(&version.to_string()),
+        }
+    }
+
+    fn remove_version(&self, version: &Version) -> Result<(), SvmError> {
+        match &self.dir {
+            Some(dir) => Ok(fs::remove_dir_all(dir.join(version.to_string()))?),
+            None => crate::remove_version(version),
+        }
+    }
+}
+
+fn gateway_url(gateway: &str, namespace: &str, address: &str) -> Option<Url> {
+    Url::parse(&format!(
+        "{}/{namespace}/{address}",
+        gateway.trim_end_matches('/')
+    ))
+    .ok()
+}
+
+/// Blocking version of [`install`]
+#[cfg(feature = "blocking")]
+pub fn blocking_install(version: &Version) -> Result<PathBuf, SvmError> {
+    blocking_install_with_options(version, &InstallOptions::default())
+}
+
+/// Blocking version of [`install_with_options`]
+#[cfg(feature = "blocking")]
+pub fn blocking_install_with_options(
+    version: &Version,
+    options: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    let target = options.target();
+    target.setup()?;
+
+    let (artifacts, _) =
+        crate::releases::blocking_releases_with_fallback(target.platform, &options.mirrors)?;
+    let release = artifacts.index(target.platform).resolve(version)?;
+    let artifact = &release.artifact;
+    options.ensure_trusted(artifact)?;
+
+    let mut urls = options.mirrors.urls(artifact.url.as_str())?;
+    urls.extend(options.content_urls(&release.urls));
+    let client = reqwest::blocking::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("reqwest::Client::new()");
+    let (origin, binbytes) = blocking_download(&client, &urls, |binbytes| {
+        ensure_digests(binbytes, version, &release)
+    })?;
+    ensure_executable(&binbytes, version, target.platform)?;
 
     // lock file to indicate that installation of this solc version will be in progress.
-    let lock_path = lock_file_path(version);
+    let lock_path = lock_file_path(target.dir(), version);
     // wait until lock file is released, possibly by another parallel thread trying to install the
     // same version of solc.
     let _lock = try_lock_file(lock_path)?;
 
-    do_install_and_retry(
-        version,
-        &binbytes,
-        artifact.to_string().as_str(),
-        &expected_checksum,
-    )
+    let solc_path =
+        do_install_and_retry(version, &binbytes, &artifact.name, &release.sha256, &target)?;
+    write_origin(
+        &solc_path,
+        &Origin {
+            url: origin,
+            source: artifact.source,
+            commit: release.commit().map(str::to_string),
+        },
+    )?;
+    if options.smoke_test && target.platform == platform::host_platform() {
+        smoke_test(version, release.commit(), &solc_path, &target)?;
+    }
+    Ok(solc_path)
 }
 
 /// Installs the provided version of Solc in the machine.
 ///
 /// Returns the path to the solc file.
 pub async fn install(version: &Version) -> Result<PathBuf, SvmError> {
-    setup_data_dir()?;
+    install_with_options(version, &InstallOptions::default()).await
+}
 
-    let artifacts = all_releases(platform::platform()).await?;
-    let artifact = artifacts
-        .get_artifact(version)
-        .ok_or_else(|| SvmError::UnknownVersion(version.clone()))?;
-    let download_url = artifact_url(platform::platform(), version, artifact.to_string().as_str())?;
+/// Installs the provided version of Solc in the machine, with the given options.
+///
+/// Returns the path to the solc file.
+pub async fn install_with_options(
+    version: &Version,
+    options: &InstallOptions,
+) -> Result<PathBuf, SvmError> {
+    let target = options.target();
+    target.setup()?;
 
-    let expected_checksum = artifacts
-        .get_checksum(version)
-        .unwrap_or_else(|| panic!("checksum not available: {:?}", version.to_string()));
+    let (artifacts, _) = releases_with_fallback(target.platform, &options.mirrors).await?;
+    let release = artifacts.index(target.platform).resolve(version)?;
+    let artifact = &release.artifact;
+    options.ensure_trusted(artifact)?;
 
-    let res = reqwest::Client::builder()
+    let mut urls = options.mirrors.urls(artifact.url.as_str())?;
+    urls.extend(options.content_urls(&release.urls));
+    let client = reqwest::Client::builder()
         .timeout(REQUEST_TIMEOUT)
         .build()
-        .expect("reqwest::Client::new()")
-        .get(download_url.clone())
-        .send()
-        .await?;
-
-    if !res.status().is_success() {
-        return Err(SvmError::UnsuccessfulResponse(download_url, res.status()));
-    }
-
-    let binbytes = res.bytes().await?;
-    ensure_checksum(&binbytes, version, &expected_checksum)?;
+        .expect("reqwest::Client::new()");
+    let (origin, binbytes) = download(&client, &urls, |binbytes| {
+        ensure_digests(binbytes, version, &release)
+    })
+    .await?;
+    ensure_executable(&binbytes, version, target.platform)?;
 
     // lock file to indicate that installation of this solc version will be in progress.
-    let lock_path = lock_file_path(version);
+    let lock_path = lock_file_path(target.dir(), version);
     // wait until lock file is released, possibly by another parallel thread trying to install the
     // same version of solc.
     let _lock = try_lock_file(lock_path)?;
 
-    do_install_and_retry(
-        version,
-        &binbytes,
-        artifact.to_string().as_str(),
-        &expected_checksum,
+    let solc_path =
+        do_install_and_retry(version, &binbytes, &artifact.name, &release.sha256, &target)?;
+    write_origin(
+        &solc_path,
+        &Origin {
+            url: origin,
+            source: artifact.source,
+            commit: release.commit().map(str::to_string),
+        },
+    )?;
+    if options.smoke_test && target.platform == platform::host_platform() {
+        smoke_test(version, release.commit(), &solc_path, &target)?;
+    }
+    Ok(solc_path)
+}
+
+/// Blocking version of [`download`]
+#[cfg(feature = "blocking")]
+fn blocking_download(
+    client: &reqwest::blocking::Client,
+    urls: &[Url],
+    verify: impl Fn(&[u8]) -> Result<(), SvmError>,
+) -> Result<(Url, Vec<u8>), SvmError> {
+    let mut failures = Vec::new();
+    let mut rest = urls;
+    loop {
+        let (url, res) = match mirrors::blocking_get(client, rest) {
+            Ok(served) => served,
+            Err(err) => return Err(download_failed(failures, err)),
+        };
+        let binbytes = res.bytes()?.to_vec();
+        match verify(&binbytes) {
+            Ok(()) => return Ok((url, binbytes)),
+            Err(err) if is_corrupt(&err) => failures.push(err),
+            Err(err) => return Err(err),
+        }
+        rest = after(rest, &url);
+        if rest.is_empty() {
+            return Err(mirrors::all_failed(failures));
+        }
+    }
+}
+
+/// Downloads the artifact from the first of `urls` that serves it with the expected checksums,
+/// returning the URL it was downloaded from.
+///
+/// The URLs are the mirrors of the artifact's source followed by the content address fallbacks.
+/// A mirror serving a corrupt or stale artifact is skipped like an unavailable one.
+async fn download(
+    client: &reqwest::Client,
+    urls: &[Url],
+    verify: impl Fn(&[u8]) -> Result<(), SvmError>,
+) -> Result<(Url, Vec<u8>), SvmError> {
+    let mut failures = Vec::new();
+    let mut rest = urls;
+    loop {
+        let (url, res) = match mirrors::get(client, rest).await {
+            Ok(served) => served,
+            Err(err) => return Err(download_failed(failures, err)),
+        };
+        let binbytes = res.bytes().await?.to_vec();
+        match verify(&binbytes) {
+            Ok(()) => return Ok((url, binbytes)),
+            Err(err) if is_corrupt(&err) => failures.push(err),
+            Err(err) => return Err(err),
+        }
+        rest = after(rest, &url);
+        if rest.is_empty() {
+            return Err(mirrors::all_failed(failures));
+        }
+    }
+}
+
+/// Returns whether the downloaded artifact doesn't have the expected checksums.
+const fn is_corrupt(err: &SvmError) -> bool {
+    matches!(
+        err,
+        SvmError::ChecksumMismatch { .. } | SvmError::Keccak256Mismatch { .. }
     )
 }
 
+/// Returns the URLs following `url`, which served a corrupt artifact.
+fn after<'a>(urls: &'a [Url], url: &Url) -> &'a [Url] {
+    urls.iter()
+        .position(|served| served == url)
+        .map_or(&[], |i| &urls[i + 1..])
+}
+
+/// Returns the error of a download that found no valid artifact, given the corrupt artifacts
+/// served by previous mirrors.
+fn download_failed(mut failures: Vec<SvmError>, err: SvmError) -> SvmError {
+    if failures.is_empty() || !mirrors::is_unavailable(&err) {
+        return err;
+    }
+    failures.push(err);
+    mirrors::all_failed(failures)
+}
+
+/// Records where the installed binary at `solc_path` was downloaded from, next to it.
+fn write_origin(solc_path: &Path, origin: &Origin) -> Result<(), SvmError> {
+    let origin = serde_json::to_vec(origin)?;
+    fs::write(solc_path.with_file_name(".origin"), origin)?;
+    Ok(())
+}
+
 /// Same as [`do_install`] but retries "text file busy" errors.
 fn do_install_and_retry(
     version: &Version,
     binbytes: &[u8],
     artifact: &str,
     expected_checksum: &[u8],
+    target: &InstallTarget,
 ) -> Result<PathBuf, SvmError> {
     let mut retries = 0;
 
     loop {
-        return match do_install(version, binbytes, artifact) {
+        return match do_install(version, binbytes, artifact, target) {
             Ok(path) => Ok(path),
             Err(err) => {
                 // installation failed
@@ -135,7 +486,7 @@ fn do_install_and_retry(
                 // check if this failed due to a text file busy, which indicates that a different process started using the target file
                 if err.to_string().to_lowercase().contains("text file busy") {
                     // busy solc can be in use for a while (e.g. if compiling a large project), so we check if the file exists and has the correct checksum
-                    let solc_path = version_binary(&version.to_string());
+                    let solc_path = target.solc_path(version);
                     if solc_path.exists()
                         && let Ok(content) = fs::read(&solc_path)
                         && ensure_checksum(&content, version, expected_checksum).is_ok()
@@ -155,14 +506,31 @@ fn do_install_and_retry(
     }
 }
 
-fn do_install(version: &Version, binbytes: &[u8], _artifact: &str) -> Result<PathBuf, SvmError> {
-    setup_version(&version.to_string())?;
-    let installer = Installer { version, binbytes };
+fn do_install(
+    version: &Version,
+    binbytes: &[u8],
+    artifact: &str,
+    target: &InstallTarget,
+) -> Result<PathBuf, SvmError> {
+    target.setup_version(version)?;
+    let installer = Installer {
+        version,
+        binbytes,
+        target,
+    };
 
     // Solc versions <= 0.7.1 are .zip files for Windows only
-    #[cfg(target_os = "windows")]
-    if _artifact.ends_with(".zip") {
+    if artifact.ends_with(".zip") {
+        #[cfg(target_os = "windows")]
         return installer.install_zip();
+        #[cfg(not(target_os = "windows"))]
+        return Err(SvmError::UnsupportedVersion(
+            version.to_string(),
+            format!(
+                "{} (zip archives can only be extracted on Windows)",
+                target.platform
+            ),
+        ));
     }
 
     installer.install()
@@ -196,8 +564,8 @@ impl Drop for LockFile {
 }
 
 /// Returns the lockfile to use for a specific file
-fn lock_file_path(version: &Version) -> PathBuf {
-    data_dir().join(format!(".lock-solc-{version}"))
+fn lock_file_path(dir: &Path, version: &Version) -> PathBuf {
+    dir.join(format!(".lock-solc-{version}"))
 }
 
 // Installer type that copies binary data to the appropriate solc binary file:
@@ -208,30 +576,34 @@ struct Installer<'a> {
     version: &'a Version,
     // binary data of the solc executable
     binbytes: &'a [u8],
+    // where to install the binary to
+    target: &'a InstallTarget,
 }
 
 impl Installer<'_> {
     /// Installs the solc version at the version specific destination and returns the path to the installed solc file.
     fn install(self) -> Result<PathBuf, SvmError> {
-        let named_temp_file = NamedTempFile::new_in(data_dir())?;
+        let named_temp_file = NamedTempFile::new_in(self.target.dir())?;
         let (mut f, temp_path) = named_temp_file.into_parts();
 
         #[cfg(target_family = "unix")]
         f.set_permissions(Permissions::from_mode(0o755))?;
         f.write_all(self.binbytes)?;
 
-        if platform::is_nixos()
-            && *self.version >= NIXOS_MIN_PATCH_VERSION
-            && *self.version <= NIXOS_MAX_PATCH_VERSION
+        // Dynamically linked binaries request a loader at a path which doesn't exist on NixOS.
+        if self.target.dir.is_none()
+            && platform::is_nixos()
+            && LinkInfo::parse(self.binbytes).is_ok_and(|info| info.is_interpreter_missing())
         {
             patch_for_nixos(self.version, &temp_path)?;
         }
 
-        let solc_path = version_binary(&self.version.to_string());
+        let solc_path = self.target.solc_path(self.version);
 
         // Windows requires that the old file be moved out of the way first.
         if cfg!(target_os = "windows") {
-            let temp_path = NamedTempFile::new_in(data_dir()).map(NamedTempFile::into_temp_path)?;
+            let temp_path =
+                NamedTempFile::new_in(self.target.dir()).map(NamedTempFile::into_temp_path)?;
             fs::rename(&solc_path, &temp_path).unwrap_or_default();
         }
 
@@ -244,7 +616,7 @@ impl Installer<'_> {
     /// installed solc binary.
     #[cfg(target_os = "windows")]
     fn install_zip(self) -> Result<PathBuf, SvmError> {
-        let solc_path = version_binary(&self.version.to_string());
+        let solc_path = self.target.solc_path(self.version);
         let version_path = solc_path.parent().unwrap();
 
         let mut content = std::io::Cursor::new(self.binbytes);
@@ -252,6 +624,7 @@ impl Installer<'_> {
         archive.extract(version_path)?;
 
         std::fs::rename(version_path.join("solc.exe"), &solc_path)?;
+        ensure_executable(&fs::read(&solc_path)?, self.version, self.target.platform)?;
 
         Ok(solc_path)
     }
@@ -259,71 +632,58 @@ impl Installer<'_> {
 
 /// Patch the given binary to use the dynamic linker provided by nixos.
 fn patch_for_nixos(version: &Version, bin: &Path) -> Result<(), SvmError> {
-    let dynamic_linker = nixos_dynamic_linker()?;
-    add_gc_root_for_store_path(version, &dynamic_linker)?;
-
-    let output = Command::new("nix-shell")
-        .arg("-p")
-        .arg("patchelf")
-        .arg("--run")
-        .arg(format!(
-            "patchelf --set-interpreter \"{}\" {}",
-            dynamic_linker,
-            bin.display()
+    let patch_error =
+        |reason: String| SvmError::CouldNotPatchForNixOs(bin.display().to_string(), reason);
+    let dynamic_linker = nixos_dynamic_linker().ok_or_else(|| {
+        patch_error(format!(
+            "could not find a dynamic linker, set {SVM_DYNAMIC_LINKER} or NIX_LD"
         ))
-        .output()
-        .map_err(|e| SvmError::CouldNotPatchForNixOs(String::new(), e.to_string()))?;
+    })?;
 
-    match output.status.success() {
-        true => Ok(()),
-        false => Err(SvmError::CouldNotPatchForNixOs(
-            String::from_utf8_lossy(&output.stdout).into_owned(),
-            String::from_utf8_lossy(&output.stderr).into_owned(),
-        )),
+    // Pin the linker in the Nix store so that garbage collection doesn't break the binary.
+    let dynamic_linker = fs::canonicalize(&dynamic_linker).unwrap_or(dynamic_linker);
+    if dynamic_linker.starts_with("/nix/store") {
+        add_gc_root_for_store_path(version, &dynamic_linker)?;
     }
-}
-
-/// Resolves the NixOS dynamic linker path from the nix-shell environment.
-fn nixos_dynamic_linker() -> Result<String, SvmError> {
-    let output = Command::new("nix-shell")
-        .arg("-p")
-        .arg("patchelf")
-        .arg("--run")
-        .arg("cat $NIX_CC/nix-support/dynamic-linker")
-        .output()
-        .map_err(|e| SvmError::CouldNotPatchForNixOs(String::new(), e.to_string()))?;
 
-    if !output.status.success() {
-        return Err(SvmError::CouldNotPatchForNixOs(
-            String::from_utf8_lossy(&output.stdout).into_owned(),
-            String::from_utf8_lossy(&output.stderr).into_owned(),
-        ));
-    }
+    let dynamic_linker = dynamic_linker.to_str().ok_or_else(|| {
+        patch_error(format!(
+            "dynamic linker path {} is not valid UTF-8",
+            dynamic_linker.display()
+        ))
+    })?;
+    let mut binbytes = fs::read(bin)?;
+    elf::set_interpreter(&mut binbytes, dynamic_linker).map_err(|e| patch_error(e.to_string()))?;
+    fs::write(bin, binbytes)?;
 
-    let dynamic_linker = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if dynamic_linker.is_empty() {
-        return Err(SvmError::CouldNotPatchForNixOs(
-            String::new(),
-            "empty dynamic linker path from nix-shell".to_string(),
-        ));
-    }
+    Ok(())
+}
 
-    Ok(dynamic_linker)
+/// Resolves the NixOS dynamic linker path.
+///
+/// In order of precedence, this is the path configured with [`SVM_DYNAMIC_LINKER`], the `NIX_LD`
+/// path set by `nix-ld`, or the interpreter of the current process.
+fn nixos_dynamic_linker() -> Option<PathBuf> {
+    let configured = [SVM_DYNAMIC_LINKER, "NIX_LD"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    let own = fs::read("/proc/self/exe")
+        .ok()
+        .and_then(|exe| elf::interpreter(&exe).ok().flatten())
+        .map(PathBuf::from);
+
+    configured.chain(own).find(|path| path.exists())
 }
 
 /// Adds a persistent gcroot for a nix store path used by a specific installed solc version.
-fn add_gc_root_for_store_path(version: &Version, store_path: &str) -> Result<(), SvmError> {
-    let gcroots_dir = data_dir().join(".gcroots");
-    fs::create_dir_all(&gcroots_dir)?;
+fn add_gc_root_for_store_path(version: &Version, store_path: &Path) -> Result<(), SvmError> {
+    fs::create_dir_all(gc_roots_dir())?;
 
     // One gcroot per solc version to avoid repointing a shared root when linker paths change.
-    let root_path = gcroots_dir.join(format!("solc-{version}-dynamic-linker"));
-
-    match fs::remove_file(&root_path) {
-        Ok(()) => {}
-        Err(err) if err.kind() == ErrorKind::NotFound => {}
-        Err(err) => return Err(err.into()),
-    }
+    let root_path = gc_root_path(&version.to_string());
+    remove_gc_root(&root_path)?;
 
     let output = Command::new("nix-store")
         .arg("--add-root")
@@ -343,6 +703,115 @@ fn add_gc_root_for_store_path(version: &Version, store_path: &str) -> Result<(),
     }
 }
 
+/// Runs the installed binary with `--version` and checks the reported version and commit, removing
+/// the installed version if they don't match.
+fn smoke_test(
+    version: &Version,
+    commit: Option<&str>,
+    solc_path: &Path,
+    target: &InstallTarget,
+) -> Result<(), SvmError> {
+    let result = check_solc_version(version, commit, solc_path);
+    if let Err(reason) = result {
+        target.remove_version(version)?;
+        return Err(SvmError::InstallVerificationFailed {
+            version: version.to_string(),
+            reason,
+        });
+    }
+    Ok(())
+}
+
+fn check_solc_version(
+    version: &Version,
+    expected_commit: Option<&str>,
+    solc_path: &Path,
+) -> Result<(), String> {
+    let output = Command::new(solc_path)
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("could not run {}: {e}", solc_path.display()))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`solc --version` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (actual, commit) = parse_version_output(&stdout)
+        .ok_or_else(|| format!("could not parse `solc --version` output: {}", stdout.trim()))?;
+
+    let expected = Version {
+        build: semver::BuildMetadata::EMPTY,
+        ..version.clone()
+    };
+    if actual != expected {
+        return Err(format!("binary reports version {actual}"));
+    }
+    if let Some(expected) = expected_commit
+        && commit.as_deref() != Some(expected)
+    {
+        return Err(format!(
+            "binary reports commit {}, but the artifact was built from commit {expected}",
+            commit.as_deref().unwrap_or("<none>")
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the version and commit hash from the output of `solc --version`, e.g.
+/// `Version: 0.8.10+commit.fc410830.Linux.g++`.
+pub(crate) fn parse_version_output(output: &str) -> Option<(Version, Option<String>)> {
+    let long_version = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version:"))?
+        .trim();
+    // The platform suffix, e.g. `.Linux.g++`, is not valid semver build metadata.
+    let (version, build) = long_version
+        .split_once('+')
+        .map_or((long_version, None), |(version, build)| {
+            (version, Some(build))
+        });
+    let commit = build
+        .and_then(|build| build.strip_prefix("commit."))
+        .and_then(|build| build.split('.').next())
+        .map(str::to_string);
+    Some((Version::parse(version).ok()?, commit))
+}
+
+/// Checks that the artifact is an executable that can run on `platform`, natively or emulated.
+///
+/// Zip archives, only used for old Windows releases, are checked once extracted.
+fn ensure_executable(
+    binbytes: &[u8],
+    version: &Version,
+    platform: Platform,
+) -> Result<(), SvmError> {
+    if binbytes.starts_with(b"PK\x03\x04") {
+        return Ok(());
+    }
+    let info = BinaryInfo::parse(binbytes)?;
+    if info.execution(platform).is_none() {
+        return Err(SvmError::PlatformMismatch {
+            version: version.to_string(),
+            platform: platform.to_string(),
+            actual: info.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks the SHA-256 and, if recorded, Keccak-256 checksums of a downloaded artifact.
+fn ensure_digests(binbytes: &[u8], version: &Version, release: &Release) -> Result<(), SvmError> {
+    ensure_checksum(binbytes, version, &release.sha256)?;
+    if let Some(keccak256) = &release.keccak256 {
+        ensure_keccak256(binbytes, version, keccak256)?;
+    }
+    Ok(())
+}
+
 fn ensure_checksum(
     binbytes: &[u8],
     version: &Version,
@@ -362,9 +831,27 @@ fn ensure_checksum(
     Ok(())
 }
 
+fn ensure_keccak256(
+    binbytes: &[u8],
+    version: &Version,
+    expected_checksum: &[u8],
+) -> Result<(), SvmError> {
+    let checksum = &sha3::Keccak256::digest(binbytes)[..];
+    if checksum != expected_checksum {
+        return Err(SvmError::Keccak256Mismatch {
+            version: version.to_string(),
+            expected: hex::encode(expected_checksum),
+            actual: hex::encode(checksum),
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::all_releases;
+    use crate::paths::tests::TempDataDir;
     use rand::seq::IndexedRandom;
 
     #[allow(unused)]
@@ -522,20 +1009,15 @@ mod tests {
             .await
             .unwrap();
 
-        let artifact = artifacts.releases.get(&LATEST).unwrap();
-        let download_url = artifact_url(
-            platform::Platform::LinuxAarch64,
-            &LATEST,
-            artifact.to_string().as_str(),
-        )
-        .unwrap();
-
-        let checksum = artifacts.get_checksum(&LATEST).unwrap();
+        let release = artifacts
+            .index(platform::Platform::LinuxAarch64)
+            .resolve(&LATEST)
+            .unwrap();
 
-        let resp = reqwest::get(download_url).await.unwrap();
+        let resp = reqwest::get(release.artifact.url).await.unwrap();
         assert!(resp.status().is_success());
         let binbytes = resp.bytes().await.unwrap();
-        ensure_checksum(&binbytes, &LATEST, &checksum).unwrap();
+        ensure_checksum(&binbytes, &LATEST, &release.sha256).unwrap();
     }
 
     // Ensures we can download thirdparty linux-aarch64 solc binaries that do not have official
@@ -548,20 +1030,123 @@ mod tests {
             .await
             .unwrap();
 
-        let artifact = artifacts.releases.get(&version).unwrap();
-        let download_url = artifact_url(
-            platform::Platform::LinuxAarch64,
-            &version,
-            artifact.to_string().as_str(),
+        let release = artifacts
+            .index(platform::Platform::LinuxAarch64)
+            .resolve(&version)
+            .unwrap();
+
+        let resp = reqwest::get(release.artifact.url).await.unwrap();
+        assert!(resp.status().is_success());
+        let binbytes = resp.bytes().await.unwrap();
+        ensure_checksum(&binbytes, &version, &release.sha256).unwrap();
+    }
+
+    #[test]
+    fn parse_solc_version_output() {
+        let output = "solc, the solidity compiler commandline interface\nVersion: 0.8.10+commit.fc410830.Linux.g++\n";
+        assert_eq!(
+            parse_version_output(output),
+            Some((Version::new(0, 8, 10), Some("fc410830".to_string())))
+        );
+        assert_eq!(
+            parse_version_output("Version: 0.8.31-pre.1+commit.b59566f6.Darwin.appleclang"),
+            Some((
+                "0.8.31-pre.1".parse().unwrap(),
+                Some("b59566f6".to_string())
+            ))
+        );
+        assert_eq!(parse_version_output("solc 0.8.10"), None);
+    }
+
+    /// Installs a fake solc script printing `reported` as its version.
+    #[cfg(unix)]
+    pub(crate) fn install_fake_solc(version: &Version, reported: &str) -> PathBuf {
+        install_fake_solc_into(&InstallOptions::new().target(), version, reported)
+    }
+
+    /// Same as [`install_fake_solc`], into the tree of `target`.
+    #[cfg(unix)]
+    fn install_fake_solc_into(
+        target: &InstallTarget,
+        version: &Version,
+        reported: &str,
+    ) -> PathBuf {
+        target.setup().unwrap();
+        target.setup_version(version).unwrap();
+        let solc_path = target.solc_path(version);
+        fs::write(
+            &solc_path,
+            format!("#!/bin/sh\necho 'solc, the solidity compiler commandline interface'\necho 'Version: {reported}'\n"),
         )
         .unwrap();
+        fs::set_permissions(&solc_path, Permissions::from_mode(0o755)).unwrap();
+        solc_path
+    }
 
-        let checksum = artifacts.get_checksum(&version).unwrap();
+    #[test]
+    #[cfg(unix)]
+    fn smoke_test_rolls_back() {
+        let root = tempfile::tempdir().unwrap();
+        let version = Version::new(0, 8, 10);
+        let commit = Some("fc410830");
+        let target = InstallOptions::new().root(root.path()).target();
+
+        let solc_path =
+            install_fake_solc_into(&target, &version, "0.8.10+commit.fc410830.Linux.g++");
+        smoke_test(&version, commit, &solc_path, &target).unwrap();
+        assert!(solc_path.exists());
+
+        let solc_path =
+            install_fake_solc_into(&target, &version, "0.8.11+commit.d7f03943.Linux.g++");
+        let err = smoke_test(&version, commit, &solc_path, &target).unwrap_err();
+        assert!(
+            err.to_string().contains("binary reports version 0.8.11"),
+            "{err}"
+        );
+        assert!(!solc_path.exists());
 
-        let resp = reqwest::get(download_url).await.unwrap();
-        assert!(resp.status().is_success());
-        let binbytes = resp.bytes().await.unwrap();
-        ensure_checksum(&binbytes, &version, &checksum).unwrap();
+        let solc_path =
+            install_fake_solc_into(&target, &version, "0.8.10+commit.deadbeef.Linux.g++");
+        let err = smoke_test(&version, commit, &solc_path, &target).unwrap_err();
+        assert!(err.to_string().contains("commit deadbeef"), "{err}");
+        assert!(!solc_path.parent().unwrap().exists());
+    }
+
+    #[test]
+    fn keccak256_checksum() {
+        let version = Version::new(0, 8, 10);
+        let expected =
+            hex::decode("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+                .unwrap();
+        ensure_keccak256(b"", &version, &expected).unwrap();
+        assert!(matches!(
+            ensure_keccak256(b"solc", &version, &expected),
+            Err(SvmError::Keccak256Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn ensure_executable_platform() {
+        use crate::binary::tests::{macho_fixture, pe_fixture};
+
+        let version = Version::new(0, 8, 10);
+        let linux = crate::elf::tests::Fixture::new().build();
+        ensure_executable(&linux, &version, Platform::LinuxAmd64).unwrap();
+        let err = ensure_executable(&linux, &version, Platform::MacOsAarch64).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Solc 0.8.10 artifact is built for ELF amd64, which cannot run on macosx-aarch64"
+        );
+
+        ensure_executable(
+            &macho_fixture(0x0100_0007),
+            &version,
+            Platform::MacOsAarch64,
+        )
+        .unwrap();
+        ensure_executable(&pe_fixture(0x8664), &version, Platform::WindowsAarch64).unwrap();
+        assert!(ensure_executable(b"<html></html>", &version, Platform::LinuxAmd64).is_err());
+        ensure_executable(b"PK\x03\x04", &version, Platform::WindowsAmd64).unwrap();
     }
 
     #[tokio::test]
@@ -595,4 +1180,210 @@ mod tests {
                 .contains(&version.to_string())
         );
     }
+
+    /// Serves `routes` with their status code and body over HTTP on a local port, responding with
+    /// 404 to any other path.
+    pub(crate) fn serve(routes: Vec<(&'static str, u16, Vec<u8>)>) -> Url {
+        use std::io::{BufRead, BufReader};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut request = String::new();
+                let mut reader = BufReader::new(&stream);
+                let _ = reader.read_line(&mut request);
+                // Drain the headers.
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, ..)| *route == path)
+                    .map_or((404, &[][..]), |(_, status, body)| (*status, body));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} \r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(body);
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn official_only() {
+        let artifact = |source| Artifact {
+            version: Version::new(0, 8, 20),
+            platform: Platform::MacOsAarch64,
+            name: "solc".to_string(),
+            url: Url::parse("https://binaries.soliditylang.org/macosx-amd64/solc").unwrap(),
+            source,
+            execution: crate::Execution::Native,
+        };
+        let options = InstallOptions::new().official_only(true);
+        options.ensure_trusted(&artifact(Source::Official)).unwrap();
+        let err = options
+            .ensure_trusted(&artifact(Source::SolcBuilds))
+            .unwrap_err();
+        assert!(matches!(err, SvmError::UntrustedBuild { .. }), "{err}");
+        assert!(err.to_string().contains("alloy-rs/solc-builds"), "{err}");
+    }
+
+    #[test]
+    fn origin_roundtrip() {
+        let _data_dir = TempDataDir::new();
+        let version = Version::new(0, 8, 20);
+        setup_version(&version.to_string()).unwrap();
+        let origin = Origin {
+            url: Url::parse("https://mirror.example.com/macosx/aarch64/solc").unwrap(),
+            source: Source::SolcBuilds,
+            commit: None,
+        };
+        write_origin(&version_binary(&version.to_string()), &origin).unwrap();
+        assert_eq!(crate::version_origin(&version), Some(origin));
+        crate::remove_version(&version).unwrap();
+        assert_eq!(crate::version_origin(&version), None);
+    }
+
+    #[test]
+    fn content_urls() {
+        let urls = [
+            "bzzr://f2b3e4ac22ff9e8e1ba10e7ad45e0a0c7a0e6b0e".to_string(),
+            "dweb:/ipfs/QmbfkL8z1zvU4yyFxKHHbFYjx7xeXxMXs5BDyHGRm8avu8".to_string(),
+        ];
+        let options = InstallOptions::new().ipfs_gateway("http://127.0.0.1:8080/");
+        assert_eq!(
+            options.content_urls(&urls),
+            vec![
+                Url::parse(
+                    "http://127.0.0.1:8080/ipfs/QmbfkL8z1zvU4yyFxKHHbFYjx7xeXxMXs5BDyHGRm8avu8"
+                )
+                .unwrap()
+            ]
+        );
+
+        let options = options.swarm_gateway("http://127.0.0.1:1633");
+        assert_eq!(
+            options.content_urls(&urls)[0],
+            Url::parse("http://127.0.0.1:1633/bzz-raw:/f2b3e4ac22ff9e8e1ba10e7ad45e0a0c7a0e6b0e")
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn download_falls_back_to_gateway() {
+        let gateway = serve(vec![("/ipfs/QmSolc", 200, b"solc".to_vec())]);
+        let options = InstallOptions::new().ipfs_gateway(gateway.as_str());
+        let fallback = options.content_urls(&["dweb:/ipfs/QmSolc".to_string()]);
+        let client = reqwest::Client::new();
+
+        // The primary host is unreachable.
+        let unreachable = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            Url::parse(&format!("http://{}/solc", listener.local_addr().unwrap())).unwrap()
+        };
+        let urls = [vec![unreachable.clone()], fallback.clone()].concat();
+        assert_eq!(
+            download(&client, &urls, |_| Ok(())).await.unwrap(),
+            (fallback[0].clone(), b"solc".to_vec())
+        );
+
+        // The primary host doesn't have the artifact.
+        let missing = gateway.join("linux-amd64/solc").unwrap();
+        let urls = [vec![missing], fallback.clone()].concat();
+        assert_eq!(
+            download(&client, &urls, |_| Ok(())).await.unwrap(),
+            (fallback[0].clone(), b"solc".to_vec())
+        );
+
+        // The gateway doesn't have the artifact either.
+        let urls = [unreachable, gateway.join("ipfs/QmOther").unwrap()];
+        assert!(matches!(
+            download(&client, &urls, |_| Ok(())).await,
+            Err(SvmError::AllMirrorsFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn download_skips_corrupt_mirrors() {
+        let version = Version::new(0, 8, 20);
+        let expected = sha2::Sha256::digest(b"solc").to_vec();
+        let verify = |binbytes: &[u8]| ensure_checksum(binbytes, &version, &expected);
+        let stale = serve(vec![("/solc", 200, b"stale".to_vec())]);
+        let mirror = serve(vec![("/solc", 200, b"solc".to_vec())]);
+        let client = reqwest::Client::new();
+
+        let urls = [stale.join("solc").unwrap(), mirror.join("solc").unwrap()];
+        assert_eq!(
+            download(&client, &urls, verify).await.unwrap(),
+            (urls[1].clone(), b"solc".to_vec())
+        );
+
+        // A single corrupt mirror reports the mismatch.
+        assert!(matches!(
+            download(&client, &urls[..1], verify).await,
+            Err(SvmError::ChecksumMismatch { .. })
+        ));
+
+        // Corrupt and unavailable mirrors are reported together.
+        let urls = [stale.join("solc").unwrap(), mirror.join("missing").unwrap()];
+        assert!(matches!(
+            download(&client, &urls, verify).await,
+            Err(SvmError::AllMirrorsFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn install_for_other_platform() {
+        let version = Version::new(0, 8, 20);
+        let name = "solc-v0.8.20+commit.a1b79de6";
+        let binary = crate::binary::tests::macho_fixture(0x0100_0007);
+        let list = format!(
+            r#"{{"builds":[{{"path":"{name}","version":"{version}","sha256":"{}"}}],"releases":{{"{version}":"{name}"}}}}"#,
+            hex::encode(sha2::Sha256::digest(&binary))
+        );
+        let mirror = serve(vec![
+            ("/macosx-amd64/list.json", 200, list.clone().into_bytes()),
+            (
+                "/macosx-amd64/solc-v0.8.20+commit.a1b79de6",
+                200,
+                binary.clone(),
+            ),
+            ("/windows-amd64/list.json", 200, list.into_bytes()),
+            (
+                "/windows-amd64/solc-v0.8.20+commit.a1b79de6",
+                200,
+                binary.clone(),
+            ),
+        ]);
+
+        let root = tempfile::tempdir().unwrap();
+        let options = InstallOptions::new()
+            .platform(Platform::MacOsAmd64)
+            .root(root.path())
+            .mirrors(Source::Official, [mirror.as_str()])
+            .smoke_test(true);
+        let solc_path = install_with_options(&version, &options).await.unwrap();
+        assert_eq!(
+            solc_path,
+            root.path().join("macosx-amd64/0.8.20/solc-0.8.20")
+        );
+        assert_eq!(fs::read(&solc_path).unwrap(), binary);
+        let origin: Origin =
+            serde_json::from_slice(&fs::read(solc_path.with_file_name(".origin")).unwrap())
+                .unwrap();
+        assert_eq!(origin.source, Source::Official);
+        assert!(origin.url.as_str().starts_with(mirror.as_str()));
+
+        // The artifact must be able to run on the target platform.
+        let options = options.platform(Platform::WindowsAmd64);
+        let err = install_with_options(&version, &options).await.unwrap_err();
+        assert!(matches!(err, SvmError::PlatformMismatch { .. }), "{err}");
+        assert!(!root.path().join("windows-amd64/0.8.20").exists());
+    }
 }
@@ -12,27 +12,91 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 use semver::Version;
-use std::fs;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+mod availability;
+pub use availability::{Availability, availability};
+
+#[cfg(feature = "blocking")]
+pub use availability::blocking_availability;
+
+mod binary;
+pub use binary::{Arch, BinaryFormat, BinaryInfo, Execution};
+
+mod bugs;
+pub use bugs::{Bug, KnownBugs, Severity, known_bugs, refresh_known_bugs};
+
+#[cfg(feature = "blocking")]
+pub use bugs::blocking_refresh_known_bugs;
+
+mod capabilities;
+pub use capabilities::{Capabilities, EvmVersion, capabilities};
+
+#[cfg(feature = "compile")]
+mod compile;
+#[cfg(feature = "compile")]
+pub use compile::{
+    Bytecode, CompilerError, Contract, ErrorSeverity, Evm, Optimizer, Settings, SourceFile,
+    SourceLocation, SourceOutput, StandardJsonInput, StandardJsonOutput, compile, compile_with,
+    compile_with_runner,
+};
+
+#[cfg(all(feature = "compile", feature = "blocking"))]
+pub use compile::blocking_compile;
+
+mod diff;
+pub use diff::{ReleaseChange, ReleasesDiff};
+
+mod elf;
+pub use elf::{HostIncompatibility, Libc, LinkInfo};
 
 mod error;
 pub use error::SvmError;
 
 mod install;
+pub use install::{
+    InstallOptions, Origin, SVM_DYNAMIC_LINKER, SVM_IPFS_GATEWAY, SVM_OFFICIAL_ONLY,
+    SVM_SWARM_GATEWAY, install, install_with_options,
+};
 #[cfg(feature = "blocking")]
-pub use install::blocking_install;
-pub use install::install;
+pub use install::{blocking_install, blocking_install_with_options};
+
+mod mirrors;
+pub use mirrors::{Source, Trust};
 
 mod paths;
 pub use paths::{data_dir, global_version_path, setup_data_dir, version_binary, version_path};
+use paths::{gc_root_path, gc_roots_dir, version_origin_path};
 
 mod platform;
-pub use platform::{Platform, platform};
+pub use platform::{Platform, SVM_PLATFORM, host_platform, platform, set_platform};
+
+mod probe;
+pub use probe::{SolcFlags, probe_binary, probe_flags};
+
+mod release;
+pub use release::{Release, ReleaseIndex};
 
 mod releases;
-pub use releases::{BuildInfo, Releases, all_releases};
+pub use releases::{
+    Artifact, BuildInfo, Releases, ReleasesOrigin, all_releases, all_releases_with_origin,
+    artifact_execution, artifact_source, artifact_url, snapshot_releases, without_build,
+};
 
 #[cfg(feature = "blocking")]
-pub use releases::blocking_all_releases;
+pub use releases::{blocking_all_releases, blocking_all_releases_with_origin};
+
+mod runner;
+pub use runner::{RunOutput, Runner};
+
+mod signature;
+pub use signature::{SVM_RELEASES_PUBLIC_KEYS, SVM_REQUIRE_SIGNATURES};
+
+mod validation;
+pub use validation::{ReleaseIssue, SVM_STRICT_RELEASES, check_release_list};
 
 #[cfg(feature = "cli")]
 #[doc(hidden)]
@@ -74,7 +138,9 @@ pub fn installed_versions() -> Result<Vec<Version>, SvmError> {
         let Some(file_name) = file_name.to_str() else {
             continue;
         };
-        if file_name == ".global-version" {
+        // Skip `.global-version`, `.gcroots` and in-progress `.lock-solc-*` files, as well as the
+        // trees of binaries installed for other platforms.
+        if file_name.starts_with('.') || file_name.parse::<Platform>().is_ok() {
             continue;
         }
         versions.push(Version::parse(file_name)?);
@@ -83,6 +149,36 @@ pub fn installed_versions() -> Result<Vec<Version>, SvmError> {
     Ok(versions)
 }
 
+/// Returns the path to the installed binary of the provided version of Solc.
+///
+/// A version with build metadata, e.g. `0.8.7+commit.e28d00a7`, resolves to a build installed
+/// side by side under its long version, or to the binary of its numeric version if that one was
+/// installed from the same commit.
+pub fn find_version_binary(version: &Version) -> Result<PathBuf, SvmError> {
+    let binary = version_binary(&version.to_string());
+    if binary.exists() {
+        return Ok(binary);
+    }
+    if !version.build.is_empty() {
+        let numeric = without_build(version);
+        let binary = version_binary(&numeric.to_string());
+        if binary.exists()
+            && let Some(commit) = releases::metadata_commit(version.build.as_str())
+            && version_origin(&numeric).is_some_and(|origin| origin.commit() == Some(commit))
+        {
+            return Ok(binary);
+        }
+    }
+    Err(SvmError::VersionNotInstalled(version.to_string()))
+}
+
+/// Returns where the installed binary of the provided version of Solc was downloaded from, if it
+/// was recorded.
+pub fn version_origin(version: &Version) -> Option<Origin> {
+    let origin = fs::read(version_origin_path(&version.to_string())).ok()?;
+    serde_json::from_slice(&origin).ok()
+}
+
 /// Blocking version of [`all_versions`]
 #[cfg(feature = "blocking")]
 pub fn blocking_all_versions() -> Result<Vec<Version>, SvmError> {
@@ -98,8 +194,50 @@ pub async fn all_versions() -> Result<Vec<Version>, SvmError> {
 }
 
 /// Removes the provided version of Solc from the machine.
+///
+/// This also drops the NixOS gcroot pinning the version's dynamic linker, if any.
 pub fn remove_version(version: &Version) -> Result<(), SvmError> {
-    fs::remove_dir_all(version_path(version.to_string().as_str())).map_err(Into::into)
+    let version = version.to_string();
+    fs::remove_dir_all(version_path(&version))?;
+    remove_gc_root(&gc_root_path(&version))
+}
+
+/// Removes the NixOS gcroots of Solc versions that are no longer installed.
+///
+/// Versions removed by older releases of svm, or by deleting their directory manually, leave their
+/// gcroot behind, which keeps the dynamic linker pinned in the Nix store forever.
+pub fn remove_stale_gc_roots() -> Result<(), SvmError> {
+    let entries = match fs::read_dir(gc_roots_dir()) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let installed = installed_versions()?;
+    for entry in entries {
+        let path = entry?.path();
+        let Some(version) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("solc-"))
+            .and_then(|name| name.strip_suffix("-dynamic-linker"))
+            .and_then(|version| Version::parse(version).ok())
+        else {
+            continue;
+        };
+        if !installed.contains(&version) {
+            remove_gc_root(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes a gcroot symlink, ignoring it if it doesn't exist.
+fn remove_gc_root(root_path: &Path) -> Result<(), SvmError> {
+    match fs::remove_file(root_path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
 }
 
 fn setup_version(version: &str) -> Result<(), SvmError> {
@@ -109,3 +247,116 @@ fn setup_version(version: &str) -> Result<(), SvmError> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paths::tests::TempDataDir;
+
+    fn create_gc_root(version: &str) -> std::path::PathBuf {
+        fs::create_dir_all(gc_roots_dir()).unwrap();
+        let root_path = gc_root_path(version);
+        // gcroots are symlinks into the Nix store, which may dangle after garbage collection.
+        #[cfg(unix)]
+        std::os::unix::fs::symlink("/nix/store/does-not-exist", &root_path).unwrap();
+        #[cfg(not(unix))]
+        fs::write(&root_path, "").unwrap();
+        root_path
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn find_long_version_binary() {
+        let _data_dir = TempDataDir::new();
+        let numeric = Version::new(0, 8, 7);
+        let long = Version::parse("0.8.7+commit.e28d00a7").unwrap();
+        let other = Version::parse("0.8.7+commit.0123abcd").unwrap();
+
+        assert!(find_version_binary(&numeric).is_err());
+        assert!(find_version_binary(&long).is_err());
+
+        // The numeric version is used if it was installed from the same commit.
+        let solc_path =
+            install::tests::install_fake_solc(&numeric, "0.8.7+commit.e28d00a7.Linux.g++");
+        assert_eq!(find_version_binary(&numeric).unwrap(), solc_path);
+        assert!(find_version_binary(&long).is_err());
+        let origin = Origin {
+            url: "https://binaries.soliditylang.org/linux-amd64/solc-linux-amd64-v0.8.7+commit.e28d00a7"
+                .parse()
+                .unwrap(),
+            source: Source::Official,
+            commit: None,
+        };
+        fs::write(
+            version_origin_path(&numeric.to_string()),
+            serde_json::to_vec(&origin).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(find_version_binary(&long).unwrap(), solc_path);
+        assert!(find_version_binary(&other).is_err());
+
+        // The commit recorded at install time takes precedence over the artifact name.
+        let origin = Origin {
+            commit: Some("0123abcd".to_string()),
+            ..origin
+        };
+        fs::write(
+            version_origin_path(&numeric.to_string()),
+            serde_json::to_vec(&origin).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(find_version_binary(&other).unwrap(), solc_path);
+        assert!(find_version_binary(&long).is_err());
+
+        // Builds of the same numeric version live side by side.
+        let other_path =
+            install::tests::install_fake_solc(&other, "0.8.7+commit.0123abcd.Linux.g++");
+        assert_eq!(other_path, version_binary("0.8.7+commit.0123abcd"));
+        assert_eq!(find_version_binary(&other).unwrap(), other_path);
+        assert_eq!(find_version_binary(&numeric).unwrap(), solc_path);
+        assert!(installed_versions().unwrap().contains(&other));
+    }
+
+    #[test]
+    fn remove_version_drops_gc_root() {
+        let _data_dir = TempDataDir::new();
+        let version = Version::new(0, 8, 20);
+        setup_version(&version.to_string()).unwrap();
+        let root_path = create_gc_root(&version.to_string());
+
+        assert!(installed_versions().unwrap().contains(&version));
+        remove_version(&version).unwrap();
+        assert!(!version_path(&version.to_string()).exists());
+        assert!(root_path.symlink_metadata().is_err());
+    }
+
+    #[test]
+    fn installed_versions_skips_platform_trees() {
+        let _data_dir = TempDataDir::new();
+        let tree = data_dir()
+            .join(Platform::LinuxAarch64.to_string())
+            .join("0.8.20");
+        fs::create_dir_all(&tree).unwrap();
+        assert!(
+            !installed_versions()
+                .unwrap()
+                .contains(&Version::new(0, 8, 20))
+        );
+    }
+
+    #[test]
+    fn remove_stale_gc_roots_keeps_installed() {
+        let _data_dir = TempDataDir::new();
+        let installed = Version::new(0, 8, 21);
+        setup_version(&installed.to_string()).unwrap();
+        let kept = create_gc_root(&installed.to_string());
+        let stale = create_gc_root("0.8.22");
+        let unrelated = gc_roots_dir().join("unrelated");
+        fs::write(&unrelated, "").unwrap();
+
+        remove_stale_gc_roots().unwrap();
+        assert!(kept.symlink_metadata().is_ok());
+        assert!(stale.symlink_metadata().is_err());
+        assert!(unrelated.exists());
+    }
+}
@@ -0,0 +1,347 @@
+use crate::SvmError;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use url::Url;
+
+/// A host that Solc release lists and artifacts are downloaded from.
+///
+/// Each source can be served by an ordered list of mirrors, configured with a comma-separated list
+/// of base URLs in the source's [`mirrors_env`](Self::mirrors_env) environment variable, e.g.
+/// `SVM_OFFICIAL_MIRRORS=https://solc.example.com,https://binaries.soliditylang.org`.
+///
+/// The mirrors are tried in turn on connection errors, `404 Not Found` and server errors. The
+/// default URL of the source is only used if no mirrors are configured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source {
+    /// The official Solidity releases at <https://binaries.soliditylang.org>.
+    Official,
+    /// Old Linux amd64 releases from <https://github.com/crytic/solc>.
+    Crytic,
+    /// Linux aarch64 releases before 0.8.31 from <https://github.com/nikitastupin/solc>.
+    Nikitastupin,
+    /// macOS aarch64 and Android releases from <https://github.com/alloy-rs/solc-builds>.
+    SolcBuilds,
+}
+
+impl Source {
+    /// All sources.
+    pub const ALL: [Self; 4] = [
+        Self::Official,
+        Self::Crytic,
+        Self::Nikitastupin,
+        Self::SolcBuilds,
+    ];
+
+    /// Returns the default base URL of the source.
+    pub const fn url(self) -> &'static str {
+        match self {
+            Self::Official => "https://binaries.soliditylang.org",
+            Self::Crytic => "https://raw.githubusercontent.com/crytic/solc",
+            Self::Nikitastupin => "https://raw.githubusercontent.com/nikitastupin/solc",
+            Self::SolcBuilds => "https://raw.githubusercontent.com/alloy-rs/solc-builds",
+        }
+    }
+
+    /// Returns who builds and publishes the artifacts of the source.
+    pub const fn provider(self) -> &'static str {
+        match self {
+            Self::Official => "soliditylang.org",
+            Self::Crytic => "crytic/solc",
+            Self::Nikitastupin => "nikitastupin/solc",
+            Self::SolcBuilds => "alloy-rs/solc-builds",
+        }
+    }
+
+    /// Returns whether the artifacts of the source are official Solidity builds.
+    pub const fn trust(self) -> Trust {
+        match self {
+            Self::Official => Trust::Official,
+            Self::Crytic | Self::Nikitastupin | Self::SolcBuilds => Trust::Community,
+        }
+    }
+
+    /// Returns the environment variable with the mirrors of the source.
+    pub const fn mirrors_env(self) -> &'static str {
+        match self {
+            Self::Official => "SVM_OFFICIAL_MIRRORS",
+            Self::Crytic => "SVM_CRYTIC_MIRRORS",
+            Self::Nikitastupin => "SVM_NIKITASTUPIN_MIRRORS",
+            Self::SolcBuilds => "SVM_SOLC_BUILDS_MIRRORS",
+        }
+    }
+
+    /// Returns the base URLs of the mirrors of the source, in the order they're tried.
+    pub fn mirrors(self) -> Vec<String> {
+        let mirrors = std::env::var(self.mirrors_env())
+            .map(|mirrors| parse_mirrors(&mirrors))
+            .unwrap_or_default();
+        if mirrors.is_empty() {
+            vec![self.url().to_string()]
+        } else {
+            mirrors
+        }
+    }
+
+    /// Returns the source serving `url` and the path of `url` relative to it.
+    fn of(url: &str) -> Option<(Self, &str)> {
+        Self::ALL.into_iter().find_map(|source| {
+            let path = url.strip_prefix(source.url())?;
+            (path.is_empty() || path.starts_with('/')).then_some((source, path))
+        })
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.provider())
+    }
+}
+
+/// Whether an artifact is built by the Solidity project or by a third party.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Trust {
+    /// Built and published by the Solidity project.
+    Official,
+    /// Built and published by a third party.
+    Community,
+}
+
+impl fmt::Display for Trust {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Official => "official",
+            Self::Community => "community",
+        })
+    }
+}
+
+fn parse_mirrors(mirrors: &str) -> Vec<String> {
+    mirrors
+        .split(',')
+        .map(|mirror| mirror.trim().trim_end_matches('/'))
+        .filter(|mirror| !mirror.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Mirrors set programmatically for some sources, taking precedence over their
+/// [`mirrors_env`](Source::mirrors_env).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MirrorOverrides(Vec<(Source, Vec<String>)>);
+
+impl MirrorOverrides {
+    /// Sets the mirrors of `source`, replacing any previously set ones.
+    pub(crate) fn set(&mut self, source: Source, mirrors: Vec<String>) {
+        self.0.retain(|(overridden, _)| *overridden != source);
+        self.0.push((source, mirrors));
+    }
+
+    /// Returns whether no source has overridden mirrors.
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the base URLs of the mirrors of `source`, in the order they're tried.
+    fn mirrors(&self, source: Source) -> Vec<String> {
+        self.0
+            .iter()
+            .find(|(overridden, _)| *overridden == source)
+            .map_or_else(|| source.mirrors(), |(_, mirrors)| mirrors.clone())
+    }
+
+    /// Returns the URLs of `url` on each mirror of its source, in the order they're tried.
+    pub(crate) fn urls(&self, url: &str) -> Result<Vec<Url>, SvmError> {
+        match Source::of(url) {
+            Some((source, path)) => with_mirrors(&self.mirrors(source), path),
+            None => Ok(vec![Url::parse(url)?]),
+        }
+    }
+}
+
+fn with_mirrors(mirrors: &[String], path: &str) -> Result<Vec<Url>, SvmError> {
+    mirrors
+        .iter()
+        .map(|mirror| Ok(Url::parse(&format!("{mirror}{path}"))?))
+        .collect()
+}
+
+/// Blocking version of [`get`]
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_get(
+    client: &reqwest::blocking::Client,
+    urls: &[Url],
+) -> Result<(Url, reqwest::blocking::Response), SvmError> {
+    let mut failures = Vec::new();
+    for url in urls {
+        let err = match client.get(url.clone()).send() {
+            Ok(res) if res.status().is_success() => return Ok((url.clone(), res)),
+            Ok(res) => SvmError::UnsuccessfulResponse(url.clone(), res.status()),
+            Err(err) => err.into(),
+        };
+        if !is_unavailable(&err) {
+            return Err(err);
+        }
+        failures.push(err);
+    }
+    Err(all_failed(failures))
+}
+
+/// Sends a GET request to each of `urls` in turn until one responds successfully, returning the
+/// URL that served the response.
+///
+/// Connection errors, timeouts, `404 Not Found` and server errors move on to the next URL, any
+/// other failure is returned right away.
+pub(crate) async fn get(
+    client: &reqwest::Client,
+    urls: &[Url],
+) -> Result<(Url, reqwest::Response), SvmError> {
+    let mut failures = Vec::new();
+    for url in urls {
+        let err = match client.get(url.clone()).send().await {
+            Ok(res) if res.status().is_success() => return Ok((url.clone(), res)),
+            Ok(res) => SvmError::UnsuccessfulResponse(url.clone(), res.status()),
+            Err(err) => err.into(),
+        };
+        if !is_unavailable(&err) {
+            return Err(err);
+        }
+        failures.push(err);
+    }
+    Err(all_failed(failures))
+}
+
+/// Returns whether the error means that the resource is unavailable at this URL, but may be at
+/// another one.
+pub(crate) fn is_unavailable(err: &SvmError) -> bool {
+    match err {
+        // An aggregated failure means every mirror was unavailable.
+        SvmError::AllMirrorsFailed(_) => true,
+        SvmError::UnsuccessfulResponse(_, status) => {
+            *status == StatusCode::NOT_FOUND || status.is_server_error()
+        }
+        SvmError::ReqwestError(err) => err.is_connect() || err.is_timeout(),
+        _ => false,
+    }
+}
+
+/// Returns the error of a single URL as is, or all of them aggregated.
+pub(crate) fn all_failed(mut failures: Vec<SvmError>) -> SvmError {
+    if failures.len() == 1 {
+        return failures.remove(0);
+    }
+    SvmError::AllMirrorsFailed(
+        failures
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::tests::serve;
+
+    #[test]
+    fn source_of_url() {
+        assert_eq!(
+            Source::of("https://binaries.soliditylang.org/linux-amd64/list.json"),
+            Some((Source::Official, "/linux-amd64/list.json"))
+        );
+        assert_eq!(
+            Source::of("https://raw.githubusercontent.com/nikitastupin/solc/2287d43/linux/aarch64"),
+            Some((Source::Nikitastupin, "/2287d43/linux/aarch64"))
+        );
+        assert_eq!(
+            Source::of("https://raw.githubusercontent.com/crytic/solc-old/master"),
+            None
+        );
+        assert_eq!(Source::of("https://example.com/solc"), None);
+    }
+
+    #[test]
+    fn mirror_list() {
+        let mirrors = parse_mirrors(" https://a.example.com/solc/ ,,https://b.example.com");
+        assert_eq!(
+            mirrors,
+            ["https://a.example.com/solc", "https://b.example.com"]
+        );
+        assert_eq!(
+            with_mirrors(&mirrors, "/linux-amd64/list.json").unwrap(),
+            [
+                Url::parse("https://a.example.com/solc/linux-amd64/list.json").unwrap(),
+                Url::parse("https://b.example.com/linux-amd64/list.json").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn mirror_overrides() {
+        let mut overrides = MirrorOverrides::default();
+        overrides.set(Source::Official, vec!["http://a.example.com".to_string()]);
+        overrides.set(
+            Source::Official,
+            parse_mirrors("http://127.0.0.1:8080/solc/,http://b.example.com"),
+        );
+        assert_eq!(
+            overrides
+                .urls("https://binaries.soliditylang.org/linux-amd64/list.json")
+                .unwrap(),
+            [
+                Url::parse("http://127.0.0.1:8080/solc/linux-amd64/list.json").unwrap(),
+                Url::parse("http://b.example.com/linux-amd64/list.json").unwrap(),
+            ]
+        );
+        assert_eq!(overrides.mirrors(Source::Crytic), Source::Crytic.mirrors());
+    }
+
+    #[tokio::test]
+    async fn get_fails_over() {
+        let down = serve(vec![("/list.json", 503, vec![])]);
+        let missing = serve(vec![]);
+        let forbidden = serve(vec![("/list.json", 403, vec![])]);
+        let up = serve(vec![("/list.json", 200, b"{}".to_vec())]);
+        let client = reqwest::Client::new();
+        let urls = |hosts: &[&Url]| {
+            hosts
+                .iter()
+                .map(|host| host.join("list.json").unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let (url, res) = get(&client, &urls(&[&down, &missing, &up])).await.unwrap();
+        assert_eq!(url, up.join("list.json").unwrap());
+        assert_eq!(res.text().await.unwrap(), "{}");
+
+        // Other errors are returned right away.
+        let err = get(&client, &urls(&[&down, &forbidden, &up]))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SvmError::UnsuccessfulResponse(_, StatusCode::FORBIDDEN)
+            ),
+            "{err}"
+        );
+
+        // A single failure is returned as is, several are aggregated.
+        let err = get(&client, &urls(&[&missing])).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SvmError::UnsuccessfulResponse(_, StatusCode::NOT_FOUND)
+            ),
+            "{err}"
+        );
+        let err = get(&client, &urls(&[&down, &missing])).await.unwrap_err();
+        let SvmError::AllMirrorsFailed(errors) = &err else {
+            panic!("{err}");
+        };
+        assert!(errors.contains("503"), "{errors}");
+        assert!(errors.contains("404"), "{errors}");
+    }
+}
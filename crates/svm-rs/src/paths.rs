@@ -1,4 +1,4 @@
-use crate::SvmError;
+use crate::{Platform, SvmError};
 use std::{
     ffi::OsString,
     fs, io,
@@ -39,6 +39,10 @@ pub fn setup_data_dir() -> Result<(), SvmError> {
 ///
 /// Returns `~/.svm` if it exists, otherwise uses `$XDG_DATA_HOME/svm`.
 pub fn data_dir() -> &'static Path {
+    #[cfg(test)]
+    if let Some(dir) = tests::DATA_DIR.get() {
+        return dir;
+    }
     static ONCE: OnceLock<PathBuf> = OnceLock::new();
     ONCE.get_or_init(|| {
         #[cfg(test)]
@@ -69,10 +73,44 @@ fn resolve_data_dir() -> PathBuf {
 
 /// Returns the path to the global version file.
 pub fn global_version_path() -> &'static Path {
+    #[cfg(test)]
+    if tests::DATA_DIR.get().is_some() {
+        return Box::leak(data_dir().join(".global-version").into_boxed_path());
+    }
     static ONCE: OnceLock<PathBuf> = OnceLock::new();
     ONCE.get_or_init(|| data_dir().join(".global-version"))
 }
 
+/// Returns the path to the directory holding the NixOS gcroots created by svm.
+///
+/// This is currently `data_dir() / .gcroots`.
+pub(crate) fn gc_roots_dir() -> PathBuf {
+    data_dir().join(".gcroots")
+}
+
+/// Returns the path to the NixOS gcroot pinning the dynamic linker of a specific Solc version.
+///
+/// This is currently `data_dir() / .gcroots / solc-{version}-dynamic-linker`.
+pub(crate) fn gc_root_path(version: &str) -> PathBuf {
+    gc_roots_dir().join(format!("solc-{version}-dynamic-linker"))
+}
+
+/// Returns the path to the release list of a platform cached by the last successful fetch.
+///
+/// This is currently `data_dir() / .releases / {platform}.json`.
+pub(crate) fn releases_cache_path(platform: Platform) -> PathBuf {
+    data_dir()
+        .join(".releases")
+        .join(format!("{platform}.json"))
+}
+
+/// Returns the path to the known bugs fetched by the last refresh.
+///
+/// This is currently `data_dir() / .bugs`.
+pub(crate) fn known_bugs_dir() -> PathBuf {
+    data_dir().join(".bugs")
+}
+
 /// Returns the path to a specific Solc version's directory.
 ///
 /// Note that this is not the path to the actual Solc binary file;
@@ -83,6 +121,13 @@ pub fn version_path(version: &str) -> PathBuf {
     data_dir().join(version)
 }
 
+/// Returns the path to the file recording the URL a specific Solc version was downloaded from.
+///
+/// This is currently `data_dir() / {version} / .origin`.
+pub(crate) fn version_origin_path(version: &str) -> PathBuf {
+    version_path(version).join(".origin")
+}
+
 /// Derive path to a specific Solc version's binary file.
 ///
 /// This is currently `data_dir() / {version} / solc-{version}`.
@@ -103,3 +148,37 @@ pub fn version_binary(version: &str) -> PathBuf {
     binary.push(version);
     PathBuf::from(binary)
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        /// The data directory of tests running on this thread, see [`TempDataDir`].
+        pub(super) static DATA_DIR: Cell<Option<&'static Path>> = const { Cell::new(None) };
+    }
+
+    /// Points [`data_dir`] at a fresh temporary directory on the current thread until dropped.
+    ///
+    /// Tests that install or remove versions use it to stay isolated from each other.
+    pub(crate) struct TempDataDir {
+        _dir: tempfile::TempDir,
+    }
+
+    impl TempDataDir {
+        pub(crate) fn new() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let data_dir = Box::leak(dir.path().join(".svm").into_boxed_path());
+            DATA_DIR.set(Some(data_dir));
+            setup_data_dir().unwrap();
+            Self { _dir: dir }
+        }
+    }
+
+    impl Drop for TempDataDir {
+        fn drop(&mut self) {
+            DATA_DIR.set(None);
+        }
+    }
+}
@@ -1,9 +1,25 @@
+use crate::{Libc, LinkInfo};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
 use std::fmt::Formatter;
 use std::str::FromStr;
-use std::{env, fmt};
+use std::sync::RwLock;
+use std::{env, fmt, fs};
+
+/// Environment variable overriding the platform returned by [`platform`], e.g. `linux-aarch64`
+/// or a Rust target triple like `aarch64-unknown-linux-gnu`.
+///
+/// Unrecognized values resolve to [`Platform::Unsupported`].
+pub const SVM_PLATFORM: &str = "SVM_PLATFORM";
+
+/// The platform set with [`set_platform`], taking precedence over [`SVM_PLATFORM`].
+static PLATFORM_OVERRIDE: RwLock<Option<Platform>> = RwLock::new(None);
 
 /// Types of supported platforms.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// Platforms are displayed, parsed and serialized as the directory names of the official release
+/// lists, e.g. `linux-amd64`. Parsing also accepts the official `linux-arm64` spelling and Rust
+/// target triples, see [`from_target_triple`](Self::from_target_triple).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum Platform {
     LinuxAmd64,
@@ -16,6 +32,57 @@ pub enum Platform {
     Unsupported,
 }
 
+impl Platform {
+    /// All supported platforms, i.e. every platform but [`Unsupported`](Self::Unsupported).
+    pub const ALL: [Self; 7] = [
+        Self::LinuxAmd64,
+        Self::LinuxAarch64,
+        Self::MacOsAmd64,
+        Self::MacOsAarch64,
+        Self::WindowsAmd64,
+        Self::WindowsAarch64,
+        Self::AndroidAarch64,
+    ];
+
+    /// Returns the platform of a Rust target triple, e.g. `aarch64-unknown-linux-gnu` or
+    /// `x86_64-apple-darwin`.
+    pub fn from_target_triple(triple: &str) -> Option<Self> {
+        let (arch, rest) = triple.split_once('-')?;
+        // Android triples also name the kernel, e.g. `aarch64-linux-android`.
+        let os = if rest.contains("android") {
+            "android"
+        } else {
+            rest.split('-').find_map(|part| match part {
+                "linux" => Some("linux"),
+                "darwin" | "macos" => Some("macos"),
+                "windows" => Some("windows"),
+                _ => None,
+            })?
+        };
+        Self::from_os_arch(os, arch)
+    }
+
+    /// Returns the platform of an operating system and architecture as named by
+    /// [`std::env::consts`], accepting the `amd64` and `arm64` aliases.
+    fn from_os_arch(os: &str, arch: &str) -> Option<Self> {
+        let arch = match arch {
+            "x86_64" | "amd64" => "x86_64",
+            "aarch64" | "arm64" => "aarch64",
+            _ => return None,
+        };
+        match (os, arch) {
+            ("linux", "x86_64") => Some(Self::LinuxAmd64),
+            ("linux", "aarch64") => Some(Self::LinuxAarch64),
+            ("macos", "x86_64") => Some(Self::MacOsAmd64),
+            ("macos", "aarch64") => Some(Self::MacOsAarch64),
+            ("windows", "x86_64") => Some(Self::WindowsAmd64),
+            ("windows", "aarch64") => Some(Self::WindowsAarch64),
+            ("android", "aarch64") => Some(Self::AndroidAarch64),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -38,17 +105,31 @@ impl FromStr for Platform {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "linux-amd64" => Ok(Self::LinuxAmd64),
-            "linux-aarch64" => Ok(Self::LinuxAarch64),
+            "linux-aarch64" | "linux-arm64" => Ok(Self::LinuxAarch64),
             "macosx-amd64" => Ok(Self::MacOsAmd64),
             "macosx-aarch64" => Ok(Self::MacOsAarch64),
             "windows-amd64" => Ok(Self::WindowsAmd64),
             "windows-aarch64" => Ok(Self::WindowsAarch64),
             "android-aarch64" => Ok(Self::AndroidAarch64),
-            s => Err(format!("unsupported platform {s}")),
+            s => Self::from_target_triple(s).ok_or_else(|| format!("unsupported platform {s}")),
         }
     }
 }
 
+impl Serialize for Platform {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Platform {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 pub fn is_nixos() -> bool {
     cfg!(target_os = "linux")
         && (std::path::Path::new("/etc/nixos").exists()
@@ -56,20 +137,55 @@ pub fn is_nixos() -> bool {
         && std::fs::read_to_string("/etc/os-release").is_ok_and(|s| s.contains("NixOS"))
 }
 
-/// Read the current machine's platform.
+/// Returns the C library used by this host, if it can be determined.
+pub(crate) fn host_libc() -> Option<Libc> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // Prefer the loader of the current process, which is absent if svm is statically linked.
+    if let Some(libc) = fs::read("/proc/self/exe")
+        .ok()
+        .and_then(|exe| LinkInfo::parse(&exe).ok())
+        .and_then(|info| info.libc())
+    {
+        return Some(libc);
+    }
+
+    fs::read_dir("/lib")
+        .ok()?
+        .flatten()
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-"))
+        .then_some(Libc::Musl)
+}
+
+/// Read the platform to manage Solc versions for.
+///
+/// This is the platform set with [`set_platform`] or [`SVM_PLATFORM`], in that order, and the
+/// current machine's platform otherwise, see [`host_platform`].
 pub fn platform() -> Platform {
-    match (env::consts::OS, env::consts::ARCH) {
-        ("linux", "x86_64") => Platform::LinuxAmd64,
-        ("linux", "aarch64") => Platform::LinuxAarch64,
-        ("macos", "x86_64") => Platform::MacOsAmd64,
-        ("macos", "aarch64") => Platform::MacOsAarch64,
-        ("windows", "x86_64") => Platform::WindowsAmd64,
-        ("windows", "aarch64") => Platform::WindowsAarch64,
-        ("android", "aarch64") => Platform::AndroidAarch64,
-        _ => Platform::Unsupported,
+    if let Some(platform) = *PLATFORM_OVERRIDE.read().unwrap_or_else(|e| e.into_inner()) {
+        return platform;
+    }
+    match env::var(SVM_PLATFORM) {
+        Ok(platform) if !platform.trim().is_empty() => {
+            platform.trim().parse().unwrap_or(Platform::Unsupported)
+        }
+        _ => host_platform(),
     }
 }
 
+/// Overrides the platform returned by [`platform`] for the rest of the process, or restores the
+/// default with `None`.
+pub fn set_platform(platform: Option<Platform>) {
+    *PLATFORM_OVERRIDE.write().unwrap_or_else(|e| e.into_inner()) = platform;
+}
+
+/// Read the current machine's platform, ignoring any override.
+pub fn host_platform() -> Platform {
+    Platform::from_os_arch(env::consts::OS, env::consts::ARCH).unwrap_or(Platform::Unsupported)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,42 +193,91 @@ mod tests {
     #[test]
     #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::LinuxAmd64);
+        assert_eq!(host_platform(), Platform::LinuxAmd64);
     }
 
     #[test]
     #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::LinuxAarch64);
+        assert_eq!(host_platform(), Platform::LinuxAarch64);
     }
 
     #[test]
     #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::MacOsAmd64);
+        assert_eq!(host_platform(), Platform::MacOsAmd64);
     }
 
     #[test]
     #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::MacOsAarch64);
+        assert_eq!(host_platform(), Platform::MacOsAarch64);
     }
 
     #[test]
     #[cfg(all(target_os = "windows", target_arch = "x86_64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::WindowsAmd64);
+        assert_eq!(host_platform(), Platform::WindowsAmd64);
     }
 
     #[test]
     #[cfg(all(target_os = "windows", target_arch = "aarch64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::WindowsAarch64);
+        assert_eq!(host_platform(), Platform::WindowsAarch64);
     }
 
     #[test]
     #[cfg(all(target_os = "android", target_arch = "aarch64"))]
     fn get_platform() {
-        assert_eq!(platform(), Platform::AndroidAarch64);
+        assert_eq!(host_platform(), Platform::AndroidAarch64);
+    }
+
+    #[test]
+    fn parse_platform() {
+        for platform in Platform::ALL {
+            assert_eq!(platform.to_string().parse::<Platform>(), Ok(platform));
+        }
+        assert_eq!("linux-arm64".parse(), Ok(Platform::LinuxAarch64));
+        assert!("Unsupported-platform".parse::<Platform>().is_err());
+        assert!("linux-riscv64".parse::<Platform>().is_err());
+    }
+
+    #[test]
+    fn target_triples() {
+        for (triple, platform) in [
+            ("x86_64-unknown-linux-gnu", Some(Platform::LinuxAmd64)),
+            ("x86_64-unknown-linux-musl", Some(Platform::LinuxAmd64)),
+            ("aarch64-unknown-linux-gnu", Some(Platform::LinuxAarch64)),
+            ("x86_64-apple-darwin", Some(Platform::MacOsAmd64)),
+            ("aarch64-apple-darwin", Some(Platform::MacOsAarch64)),
+            ("x86_64-pc-windows-msvc", Some(Platform::WindowsAmd64)),
+            ("aarch64-pc-windows-msvc", Some(Platform::WindowsAarch64)),
+            ("aarch64-linux-android", Some(Platform::AndroidAarch64)),
+            ("x86_64-linux-android", None),
+            ("riscv64gc-unknown-linux-gnu", None),
+            ("wasm32-unknown-unknown", None),
+            ("linux", None),
+        ] {
+            assert_eq!(Platform::from_target_triple(triple), platform, "{triple}");
+        }
+        assert_eq!("aarch64-apple-darwin".parse(), Ok(Platform::MacOsAarch64));
+    }
+
+    #[test]
+    fn platform_serde() {
+        let json = serde_json::to_string(&Platform::ALL).unwrap();
+        assert!(
+            json.starts_with(r#"["linux-amd64","linux-aarch64","#),
+            "{json}"
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<Platform>>(&json).unwrap(),
+            Platform::ALL
+        );
+        assert_eq!(
+            serde_json::from_str::<Platform>(r#""linux-arm64""#).unwrap(),
+            Platform::LinuxAarch64
+        );
+        assert!(serde_json::from_str::<Platform>(r#""solaris""#).is_err());
     }
 }
@@ -0,0 +1,247 @@
+use crate::{SvmError, find_version_binary, install::parse_version_output};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fs, path::Path, process::Command, time::SystemTime};
+
+/// The command line interface of a Solc binary, as reported by its `--version` and `--help`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolcFlags {
+    /// The version reported by the binary.
+    pub version: Version,
+    /// The commit the binary reports it was built from, if any.
+    pub commit: Option<String>,
+    /// `--via-ir`, to generate code through the IR.
+    pub via_ir: bool,
+    /// `--experimental-via-ir`, the name of `--via-ir` before 0.8.13.
+    pub experimental_via_ir: bool,
+    /// `--base-path`, to resolve imports relative to a directory.
+    pub base_path: bool,
+    /// `--include-path`, to resolve imports relative to additional directories.
+    pub include_path: bool,
+    /// `--allow-paths`, to allow reading files outside of the base path.
+    pub allow_paths: bool,
+    /// `--standard-json`, to read a standard JSON input from stdin.
+    pub standard_json: bool,
+    /// `--pretty-json`, to indent the standard JSON output.
+    pub pretty_json: bool,
+    /// `--evm-version`, to select the target EVM version.
+    pub evm_version: bool,
+    /// All the long flags listed by `--help`, e.g. `--optimize-runs`.
+    pub flags: BTreeSet<String>,
+}
+
+impl SolcFlags {
+    /// Parses the output of `solc --version` and `solc --help`.
+    pub fn parse(version_output: &str, help_output: &str) -> Option<Self> {
+        let (version, commit) = parse_version_output(version_output)?;
+        let flags = help_output
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | '=' | '[' | ']'))
+            .filter(|word| {
+                word.strip_prefix("--").is_some_and(|name| {
+                    name.starts_with(|c: char| c.is_ascii_alphabetic())
+                        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+            })
+            .map(str::to_string)
+            .collect::<BTreeSet<_>>();
+        let has = |flag: &str| flags.contains(flag);
+        Some(Self {
+            version,
+            commit,
+            via_ir: has("--via-ir"),
+            experimental_via_ir: has("--experimental-via-ir"),
+            base_path: has("--base-path"),
+            include_path: has("--include-path"),
+            allow_paths: has("--allow-paths"),
+            standard_json: has("--standard-json"),
+            pretty_json: has("--pretty-json"),
+            evm_version: has("--evm-version"),
+            flags,
+        })
+    }
+
+    /// Returns whether `--help` lists `flag`, given with or without its leading `--`.
+    pub fn supports(&self, flag: &str) -> bool {
+        let flag = flag.trim_start_matches('-');
+        self.flags.contains(&format!("--{flag}"))
+    }
+}
+
+/// Probes flags cached next to the binary they were probed from.
+#[derive(Serialize, Deserialize)]
+struct CachedFlags {
+    /// When the binary was last modified, to probe it again once it is replaced.
+    modified: SystemTime,
+    flags: SolcFlags,
+}
+
+/// Returns the command line interface of an installed Solc version.
+///
+/// The binary is run with `--version` and `--help` the first time, and the results are stored in
+/// the version directory for reuse until the binary is replaced.
+pub fn probe_flags(version: &Version) -> Result<SolcFlags, SvmError> {
+    let solc_path = find_version_binary(version)?;
+    let cache_path = solc_path.with_file_name(".flags");
+    let modified = fs::metadata(&solc_path)?.modified()?;
+
+    if let Ok(cached) = fs::read(&cache_path)
+        && let Ok(cached) = serde_json::from_slice::<CachedFlags>(&cached)
+        && cached.modified == modified
+    {
+        return Ok(cached.flags);
+    }
+
+    let flags = probe_binary(&solc_path)?;
+    let cached = CachedFlags {
+        modified,
+        flags: flags.clone(),
+    };
+    // Caching is best-effort, the binary can be probed again.
+    let _ = fs::write(&cache_path, serde_json::to_vec(&cached)?);
+    Ok(flags)
+}
+
+/// Runs the Solc binary at `solc_path` with `--version` and `--help` and parses its command line
+/// interface, without caching the results.
+pub fn probe_binary(solc_path: &Path) -> Result<SolcFlags, SvmError> {
+    let run = |arg: &str| -> Result<String, SvmError> {
+        let output = Command::new(solc_path).arg(arg).output()?;
+        // Old versions exit with 1 after printing `--help`.
+        if !output.status.success() && output.stdout.is_empty() {
+            return Err(SvmError::ProbeFailed {
+                path: solc_path.display().to_string(),
+                reason: format!(
+                    "`solc {arg}` exited with {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let version_output = run("--version")?;
+    let help_output = run("--help")?;
+    SolcFlags::parse(&version_output, &help_output).ok_or_else(|| SvmError::ProbeFailed {
+        path: solc_path.display().to_string(),
+        reason: format!(
+            "could not parse `solc --version` output: {}",
+            version_output.trim()
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELP_0_8_20: &str = "\
+solc, the Solidity commandline compiler.
+
+Usage: solc [options] [input_file...]
+
+General Information:
+  --help               Show help message and exit.
+  --version            Show version and exit.
+
+Input Options:
+  --base-path path     Use the given path as the root of the source tree
+                       instead of the root of the filesystem.
+  --include-path path  Make an additional source directory available to the
+                       default import callback.
+  --allow-paths path(s)
+                       Allow a given path for imports. A list of paths can be
+                       supplied by separating them with a comma.
+
+Output Formatting:
+  --pretty-json        Output JSON in pretty format.
+  --json-indent N (=2) Indent pretty-printed JSON with N spaces.
+
+Alternative Input Modes:
+  --standard-json      Switch to Standard JSON input / output mode, ignoring
+                       all options.
+
+Output Components:
+  --evm-version version (=shanghai)
+                       Select desired EVM version. Either homestead, ...
+  --via-ir             Use the new IR-based code generator.
+";
+
+    const HELP_0_4_10: &str = "\
+solc, the Solidity commandline compiler.
+Usage: solc [options] [input_file...]
+Allowed options:
+  --help               Show help message and exit.
+  --version            Show version and exit.
+  --optimize           Enable bytecode optimizer.
+  --optimize-runs n (=200)
+  --combined-json abi,asm,ast,bin,bin-runtime
+";
+
+    #[test]
+    fn parse_flags() {
+        let flags = SolcFlags::parse(
+            "solc, the solidity compiler commandline interface\nVersion: 0.8.20+commit.a1b79de6.Linux.g++\n",
+            HELP_0_8_20,
+        )
+        .unwrap();
+        assert_eq!(flags.version, Version::new(0, 8, 20));
+        assert_eq!(flags.commit.as_deref(), Some("a1b79de6"));
+        assert!(flags.via_ir && !flags.experimental_via_ir);
+        assert!(flags.base_path && flags.include_path && flags.allow_paths);
+        assert!(flags.standard_json && flags.pretty_json && flags.evm_version);
+        assert!(flags.supports("json-indent") && flags.supports("--json-indent"));
+
+        let flags =
+            SolcFlags::parse("Version: 0.4.10+commit.f0d539ae.Linux.g++", HELP_0_4_10).unwrap();
+        assert!(!flags.via_ir && !flags.base_path && !flags.standard_json);
+        assert_eq!(
+            flags.flags,
+            [
+                "--combined-json",
+                "--help",
+                "--optimize",
+                "--optimize-runs",
+                "--version"
+            ]
+            .map(String::from)
+            .into()
+        );
+
+        assert_eq!(SolcFlags::parse("solc 0.8.20", HELP_0_8_20), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn probe_installed_version() {
+        use crate::{install::tests::install_fake_solc, paths::tests::TempDataDir};
+        use std::os::unix::fs::PermissionsExt;
+
+        let _data_dir = TempDataDir::new();
+        let version = Version::new(0, 8, 20);
+        let solc_path = install_fake_solc(&version, "0.8.20+commit.a1b79de6.Linux.g++");
+        let counter = solc_path.with_file_name("runs");
+        fs::write(
+            &solc_path,
+            format!(
+                "#!/bin/sh\necho run >> '{}'\nif [ \"$1\" = --help ]; then cat <<'EOF'\n{HELP_0_8_20}EOF\nelse echo 'Version: 0.8.20+commit.a1b79de6.Linux.g++'; fi\n",
+                counter.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&solc_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let flags = probe_flags(&version).unwrap();
+        assert!(flags.via_ir);
+        assert!(solc_path.with_file_name(".flags").exists());
+
+        // The second probe is served from the cache.
+        assert_eq!(probe_flags(&version).unwrap(), flags);
+        assert_eq!(fs::read_to_string(&counter).unwrap().lines().count(), 2);
+
+        crate::remove_version(&version).unwrap();
+        assert!(matches!(
+            probe_flags(&version),
+            Err(SvmError::VersionNotInstalled(_))
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    Artifact, BuildInfo, Platform, Releases, SvmError, Trust,
+    releases::{artifact_commit, build_key, metadata_commit},
+};
+use semver::Version;
+use std::collections::HashMap;
+
+/// A Solc release for a platform, with everything needed to download and verify it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Release {
+    /// The artifact, with its version, platform, name, URL and source.
+    pub artifact: Artifact,
+    /// The full version including build metadata, e.g. `0.8.7+commit.e28d00a7`, if the release
+    /// list records it.
+    pub long_version: Option<Version>,
+    /// The SHA-256 checksum of the artifact.
+    pub sha256: Vec<u8>,
+    /// The Keccak-256 checksum of the artifact, if the release list records it.
+    pub keccak256: Option<Vec<u8>>,
+    /// Content addressed locations of the artifact, e.g. `dweb:/ipfs/...` and `bzzr://...`.
+    pub urls: Vec<String>,
+}
+
+impl Release {
+    fn new(artifact: Artifact, build_info: &BuildInfo) -> Self {
+        let long_version = build_info
+            .long_version
+            .as_deref()
+            .and_then(|long_version| Version::parse(long_version).ok())
+            .or_else(|| {
+                let build = build_info.build.as_deref()?;
+                Version::parse(&format!("{}+{build}", artifact.version)).ok()
+            });
+        Self {
+            artifact,
+            long_version,
+            sha256: build_info.sha256.clone(),
+            keccak256: build_info.keccak256.clone(),
+            urls: build_info.urls.clone(),
+        }
+    }
+
+    /// Returns the version of Solc.
+    pub const fn version(&self) -> &Version {
+        &self.artifact.version
+    }
+
+    /// Returns whether the artifact is an official Solidity build.
+    pub const fn trust(&self) -> Trust {
+        self.artifact.trust()
+    }
+
+    /// Returns the hash of the Solidity commit the artifact was built from, e.g. `e28d00a7`.
+    pub fn commit(&self) -> Option<&str> {
+        self.long_version
+            .as_ref()
+            .and_then(|long_version| metadata_commit(long_version.build.as_str()))
+            .or_else(|| artifact_commit(&self.artifact.name))
+    }
+}
+
+/// The release list of a platform, indexed for lookups by version.
+#[derive(Clone, Debug)]
+pub struct ReleaseIndex {
+    platform: Platform,
+    releases: Releases,
+    /// The positions of the builds of each numeric version and pre-release.
+    builds: HashMap<(Version, String), Vec<usize>>,
+}
+
+impl ReleaseIndex {
+    /// Indexes the releases of `platform`.
+    pub fn new(platform: Platform, releases: Releases) -> Self {
+        let mut builds = HashMap::<_, Vec<_>>::new();
+        for (index, build_info) in releases.builds.iter().enumerate() {
+            builds.entry(build_info.key()).or_default().push(index);
+        }
+        Self {
+            platform,
+            releases,
+            builds,
+        }
+    }
+
+    /// Returns the platform of the releases.
+    pub const fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    /// Returns the indexed releases.
+    pub const fn releases(&self) -> &Releases {
+        &self.releases
+    }
+
+    /// Returns the released versions, in ascending order.
+    pub fn versions(&self) -> impl Iterator<Item = &Version> {
+        self.releases.releases.keys()
+    }
+
+    /// Same as [`Releases::get_build_info`].
+    pub fn build_info(&self, version: &Version) -> Option<&BuildInfo> {
+        let builds = self.builds.get(&build_key(version))?;
+        self.releases.select_build(
+            version,
+            builds.iter().map(|index| &self.releases.builds[*index]),
+        )
+    }
+
+    /// Same as [`Releases::get_artifact`].
+    pub fn artifact(&self, version: &Version) -> Option<&String> {
+        self.releases
+            .find_artifact(version, &|version| self.build_info(version))
+    }
+
+    /// Resolves the release of a version, with the artifact [`Releases::resolve`] resolves.
+    pub fn resolve(&self, version: &Version) -> Result<Release, SvmError> {
+        let artifact = self
+            .releases
+            .find_resolve(self.platform, version, &|version| self.build_info(version))?;
+        let build_info = self
+            .build_info(version)
+            .ok_or_else(|| SvmError::ChecksumNotAvailable(version.to_string()))?;
+        Ok(Release::new(artifact, build_info))
+    }
+}
+
+impl From<ReleaseIndex> for Releases {
+    fn from(index: ReleaseIndex) -> Self {
+        index.releases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Source;
+
+    #[test]
+    fn resolve_release() {
+        let releases: Releases = serde_json::from_str(
+            r#"{
+                "builds": [
+                    {"path": "solc-macosx-amd64-v0.8.7+commit.e28d00a7", "version": "0.8.7", "build": "commit.e28d00a7", "sha256": "0x11", "keccak256": "0x22", "urls": ["dweb:/ipfs/QmSolc"]},
+                    {"path": "solc-macosx-amd64-v0.8.31-pre.1+commit.b59566f6", "version": "0.8.31", "prerelease": "pre.1", "longVersion": "0.8.31-pre.1+commit.b59566f6", "sha256": "0x33"},
+                    {"path": "solc-macosx-amd64-v0.8.8+commit.dddeac2f", "version": "0.8.8", "sha256": "0x44"}
+                ],
+                "releases": {
+                    "0.8.7": "solc-macosx-amd64-v0.8.7+commit.e28d00a7",
+                    "0.8.8": "solc-macosx-amd64-v0.8.8+commit.dddeac2f",
+                    "0.8.9": "solc-macosx-amd64-v0.8.9+commit.e5eed63a"
+                }
+            }"#,
+        )
+        .unwrap();
+        let index = releases.clone().index(Platform::MacOsAmd64);
+        let v = |version: &str| Version::parse(version).unwrap();
+
+        let release = index.resolve(&v("0.8.7")).unwrap();
+        assert_eq!(release.version(), &v("0.8.7"));
+        assert_eq!(release.long_version, Some(v("0.8.7+commit.e28d00a7")));
+        assert_eq!(release.commit(), Some("e28d00a7"));
+        assert_eq!(
+            release.artifact.url.as_str(),
+            "https://binaries.soliditylang.org/macosx-amd64/solc-macosx-amd64-v0.8.7+commit.e28d00a7"
+        );
+        assert_eq!(release.artifact.source, Source::Official);
+        assert_eq!(release.sha256, [0x11]);
+        assert_eq!(release.keccak256, Some(vec![0x22]));
+        assert_eq!(release.urls, ["dweb:/ipfs/QmSolc"]);
+
+        let release = index.resolve(&v("0.8.31-pre.1")).unwrap();
+        assert_eq!(
+            release.long_version,
+            Some(v("0.8.31-pre.1+commit.b59566f6"))
+        );
+        assert_eq!(release.sha256, [0x33]);
+
+        // The commit of lists without build metadata is read from the artifact name.
+        let release = index.resolve(&v("0.8.8+commit.dddeac2f")).unwrap();
+        assert_eq!(release.long_version, None);
+        assert_eq!(release.commit(), Some("dddeac2f"));
+        assert!(matches!(
+            index.resolve(&v("0.8.8+commit.0123abcd")),
+            Err(SvmError::UnknownVersion(_))
+        ));
+
+        assert!(matches!(
+            index.resolve(&v("0.8.9")),
+            Err(SvmError::ChecksumNotAvailable(_))
+        ));
+        assert!(matches!(
+            index.resolve(&v("0.8.10")),
+            Err(SvmError::UnknownVersion(_))
+        ));
+
+        // Indexed lookups agree with the list.
+        for version in index.versions().chain([&v("0.8.31-pre.1")]) {
+            assert_eq!(index.build_info(version), releases.get_build_info(version));
+            assert_eq!(index.artifact(version), releases.get_artifact(version));
+            if index.build_info(version).is_some() {
+                assert_eq!(
+                    index.resolve(version).unwrap().artifact,
+                    releases.resolve(Platform::MacOsAmd64, version).unwrap()
+                );
+            }
+        }
+    }
+}
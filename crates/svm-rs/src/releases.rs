@@ -1,24 +1,28 @@
-use crate::{error::SvmError, platform::Platform};
-use reqwest::get;
+use crate::{
+    Execution, ReleaseIndex, Source, Trust,
+    error::SvmError,
+    mirrors::{self, MirrorOverrides},
+    paths::releases_cache_path,
+    platform::Platform,
+    signature::{SignaturePolicy, signature_url},
+    validation::{self, ensure_valid},
+};
+use reqwest::StatusCode;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, sync::LazyLock};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    ops::{Bound, RangeBounds},
+    sync::LazyLock,
+};
 use url::Url;
 
 // Updating new releases:
-// 1. Update `https://github.com/nikitastupin/solc` commit for `linux/aarch64`
+// 1. Update the commit of `https://github.com/nikitastupin/solc` or
+//    `https://github.com/alloy-rs/solc-builds` in the platform's release sources
 // 2. Update LATEST for tests
 
-/// Base URL for all Solc releases
-/// `"SOLC_RELEASES_URL}/{platform}/list.json"`:
-/// `https://binaries.soliditylang.org/linux-amd64/list.json`
-/// `https://binaries.soliditylang.org/windows-amd64/list.json`
-/// `https://binaries.soliditylang.org/macosx-amd64/list.json`
-const SOLC_RELEASES_URL: &str = "https://binaries.soliditylang.org";
-
-const OLD_SOLC_RELEASES_DOWNLOAD_PREFIX: &str =
-    "https://raw.githubusercontent.com/crytic/solc/master/linux/amd64";
-
 const OLD_VERSION_MAX: Version = Version::new(0, 4, 9);
 
 const OLD_VERSION_MIN: Version = Version::new(0, 4, 0);
@@ -28,33 +32,211 @@ static OLD_SOLC_RELEASES: LazyLock<Releases> = LazyLock::new(|| {
         .expect("could not parse list linux-arm64-old.json")
 });
 
+/// Snapshot of the merged release list of each platform, used when neither the release sources
+/// nor the cached lists are available.
+///
+/// Regenerate it with `cargo test -p svm-rs --lib update_snapshot -- --ignored`.
+static SNAPSHOT_RELEASES: LazyLock<BTreeMap<Platform, Releases>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../list/snapshot.json"))
+        .expect("could not parse list snapshot.json")
+});
+
 const LINUX_AARCH64_MIN: Version = Version::new(0, 5, 0);
 
 // NOTE: Since version 0.8.31, Linux aarch64 releases are available: https://binaries.soliditylang.org/linux-arm64/list.json
 const LINUX_AARCH64_BINARIES: Version = Version::new(0, 8, 31);
 
-// NOTE: The official Solidity releases uses the platform name "linux-arm64"
-// instead of the "linux-aarch64" naming which is used by SVM.
-static LINUX_AARCH64_PLATFORM: &str = "linux-arm64";
-
-static LINUX_AARCH64_URL_PREFIX: &str = "https://raw.githubusercontent.com/nikitastupin/solc/2287d4326237172acf91ce42fd7ec18a67b7f512/linux/aarch64";
-
-static LINUX_AARCH64_RELEASES_URL: &str = "https://raw.githubusercontent.com/nikitastupin/solc/2287d4326237172acf91ce42fd7ec18a67b7f512/linux/aarch64/list.json";
-
 // NOTE: Since version 0.8.24, universal macosx releases are available: https://binaries.soliditylang.org/macosx-amd64/list.json
 const MACOS_AARCH64_NATIVE: Version = Version::new(0, 8, 5);
 
 const UNIVERSAL_MACOS_BINARIES: Version = Version::new(0, 8, 24);
 
-static MACOS_AARCH64_URL_PREFIX: &str = "https://raw.githubusercontent.com/alloy-rs/solc-builds/e4b80d33bc4d015b2fc3583e217fbf248b2014e1/macosx/aarch64";
+const ANDROID_AARCH64_MIN: Version = Version::new(0, 8, 24);
 
-static MACOS_AARCH64_RELEASES_URL: &str = "https://raw.githubusercontent.com/alloy-rs/solc-builds/e4b80d33bc4d015b2fc3583e217fbf248b2014e1/macosx/aarch64/list.json";
+/// Where the releases of a platform are served from, for a range of versions.
+///
+/// Each platform has an ordered table of release sources. A version is served by the first source
+/// whose range contains it, both when merging the release lists and when downloading artifacts.
+/// Versions outside all ranges are not supported on the platform.
+struct ReleaseSource {
+    /// The versions served from this source.
+    versions: (Bound<Version>, Bound<Version>),
+    /// The host serving the release list and the artifacts.
+    source: Source,
+    /// The directory of the release list and the artifacts, relative to the URL of the source.
+    dir: &'static str,
+    /// The release list bundled with svm, if it isn't fetched from `{dir}/list.json`.
+    bundled: Option<&'static LazyLock<Releases>>,
+    /// Whether the builds in the release list aren't labelled with their pre-release.
+    unlabelled_prereleases: bool,
+    /// Whether the artifacts run natively on the platform or under emulation.
+    execution: Execution,
+}
 
-const ANDROID_AARCH64_MIN: Version = Version::new(0, 8, 24);
+impl ReleaseSource {
+    const fn new(
+        versions: (Bound<Version>, Bound<Version>),
+        source: Source,
+        dir: &'static str,
+    ) -> Self {
+        Self {
+            versions,
+            source,
+            dir,
+            bundled: None,
+            unlabelled_prereleases: false,
+            execution: Execution::Native,
+        }
+    }
+
+    const fn emulated(mut self) -> Self {
+        self.execution = Execution::Emulated;
+        self
+    }
+
+    const fn bundled(mut self, releases: &'static LazyLock<Releases>) -> Self {
+        self.bundled = Some(releases);
+        self
+    }
+
+    const fn unlabelled_prereleases(mut self) -> Self {
+        self.unlabelled_prereleases = true;
+        self
+    }
+
+    /// Returns the URL of the release list, if it isn't bundled.
+    fn list_url(&self) -> Option<String> {
+        self.bundled
+            .is_none()
+            .then(|| format!("{}/{}/list.json", self.source.url(), self.dir))
+    }
+
+    /// Returns the URL of an artifact.
+    fn artifact_url(&self, artifact: &str) -> Result<Url, SvmError> {
+        Ok(Url::parse(&format!(
+            "{}/{}/{artifact}",
+            self.source.url(),
+            self.dir
+        ))?)
+    }
+}
+
+const ALL_VERSIONS: (Bound<Version>, Bound<Version>) = (Bound::Unbounded, Bound::Unbounded);
+
+static LINUX_AMD64_SOURCES: [ReleaseSource; 2] = [
+    // Old releases which were never published by the Solidity project.
+    ReleaseSource::new(
+        (
+            Bound::Included(OLD_VERSION_MIN),
+            Bound::Included(OLD_VERSION_MAX),
+        ),
+        Source::Crytic,
+        "master/linux/amd64",
+    )
+    .bundled(&OLD_SOLC_RELEASES),
+    ReleaseSource::new(ALL_VERSIONS, Source::Official, "linux-amd64"),
+];
+
+static LINUX_AARCH64_SOURCES: [ReleaseSource; 2] = [
+    // Prior to version 0.8.31, releases for Linux arm64 builds were provided by the
+    // `nikitastupin/solc` repository.
+    ReleaseSource::new(
+        (
+            Bound::Included(LINUX_AARCH64_MIN),
+            Bound::Excluded(LINUX_AARCH64_BINARIES),
+        ),
+        Source::Nikitastupin,
+        "2287d4326237172acf91ce42fd7ec18a67b7f512/linux/aarch64",
+    )
+    .unlabelled_prereleases(),
+    // NOTE: The official Solidity releases uses the platform name "linux-arm64" instead of the
+    // "linux-aarch64" naming which is used by SVM.
+    ReleaseSource::new(
+        (Bound::Included(LINUX_AARCH64_BINARIES), Bound::Unbounded),
+        Source::Official,
+        "linux-arm64",
+    ),
+];
+
+// There are no macOS artifacts of versions before 0.4.0, which are unsupported.
+static MACOS_AMD64_SOURCES: [ReleaseSource; 1] = [ReleaseSource::new(
+    (Bound::Included(OLD_VERSION_MIN), Bound::Unbounded),
+    Source::Official,
+    "macosx-amd64",
+)];
 
-static ANDROID_AARCH64_URL_PREFIX: &str = "https://raw.githubusercontent.com/alloy-rs/solc-builds/ac6f303a04b38e7ec507ced511fd3ed7a605179f/android/aarch64";
+static MACOS_AARCH64_SOURCES: [ReleaseSource; 3] = [
+    // Native builds from `alloy-rs/solc-builds`, until universal macosx releases are available.
+    // Artifacts were only ever downloaded from there for these versions, so any other version in
+    // its list is served by the official builds instead.
+    ReleaseSource::new(
+        (
+            Bound::Included(MACOS_AARCH64_NATIVE),
+            Bound::Included(UNIVERSAL_MACOS_BINARIES),
+        ),
+        Source::SolcBuilds,
+        "e4b80d33bc4d015b2fc3583e217fbf248b2014e1/macosx/aarch64",
+    ),
+    // Older versions are amd64 builds which require Rosetta support.
+    ReleaseSource::new(
+        (Bound::Unbounded, Bound::Excluded(MACOS_AARCH64_NATIVE)),
+        Source::Official,
+        "macosx-amd64",
+    )
+    .emulated(),
+    // Newer versions are universal builds.
+    ReleaseSource::new(ALL_VERSIONS, Source::Official, "macosx-amd64"),
+];
 
-static ANDROID_AARCH64_RELEASES_URL: &str = "https://raw.githubusercontent.com/alloy-rs/solc-builds/ac6f303a04b38e7ec507ced511fd3ed7a605179f/android/aarch64/list.json";
+static WINDOWS_AMD64_SOURCES: [ReleaseSource; 1] = [ReleaseSource::new(
+    ALL_VERSIONS,
+    Source::Official,
+    "windows-amd64",
+)];
+
+// Solidity does not provide native ARM64 Windows binaries, x64 binaries are used via emulation.
+static WINDOWS_AARCH64_SOURCES: [ReleaseSource; 1] =
+    [ReleaseSource::new(ALL_VERSIONS, Source::Official, "windows-amd64").emulated()];
+
+static ANDROID_AARCH64_SOURCES: [ReleaseSource; 1] = [ReleaseSource::new(
+    (Bound::Included(ANDROID_AARCH64_MIN), Bound::Unbounded),
+    Source::SolcBuilds,
+    "ac6f303a04b38e7ec507ced511fd3ed7a605179f/android/aarch64",
+)];
+
+/// Returns the release sources of a platform, in order of precedence.
+fn release_sources(platform: Platform) -> &'static [ReleaseSource] {
+    match platform {
+        Platform::LinuxAmd64 => &LINUX_AMD64_SOURCES,
+        Platform::LinuxAarch64 => &LINUX_AARCH64_SOURCES,
+        Platform::MacOsAmd64 => &MACOS_AMD64_SOURCES,
+        Platform::MacOsAarch64 => &MACOS_AARCH64_SOURCES,
+        Platform::WindowsAmd64 => &WINDOWS_AMD64_SOURCES,
+        Platform::WindowsAarch64 => &WINDOWS_AARCH64_SOURCES,
+        Platform::AndroidAarch64 => &ANDROID_AARCH64_SOURCES,
+        Platform::Unsupported => &[],
+    }
+}
+
+/// Returns the release source serving a version on a platform.
+fn release_source(platform: Platform, version: &Version) -> Option<&'static ReleaseSource> {
+    let version = without_build(version);
+    release_sources(platform)
+        .iter()
+        .find(|source| source.versions.contains(&version))
+}
+
+/// Returns whether the artifact of a version runs natively on a platform or under emulation, if
+/// the version is supported on the platform.
+pub fn artifact_execution(platform: Platform, version: &Version) -> Option<Execution> {
+    release_source(platform, version).map(|source| source.execution)
+}
+
+/// Returns the source providing the artifact of a version on a platform, if the version is
+/// supported on the platform.
+pub fn artifact_source(platform: Platform, version: &Version) -> Option<Source> {
+    release_source(platform, version).map(|source| source.source)
+}
 
 /// Defines the struct that the JSON-formatted release list can be deserialized into.
 ///
@@ -64,63 +246,150 @@ static ANDROID_AARCH64_RELEASES_URL: &str = "https://raw.githubusercontent.com/a
 /// {
 ///     "builds": [
 ///         {
+///             "path": "solc-macosx-amd64-v0.8.7+commit.e28d00a7",
 ///             "version": "0.8.7",
-///             "sha256": "0x0xcc5c663d1fe17d4eb4aca09253787ac86b8785235fca71d9200569e662677990"
+///             "build": "commit.e28d00a7",
+///             "longVersion": "0.8.7+commit.e28d00a7",
+///             "keccak256": "0x...",
+///             "sha256": "0x0xcc5c663d1fe17d4eb4aca09253787ac86b8785235fca71d9200569e662677990",
+///             "urls": [
+///                 "bzzr://...",
+///                 "dweb:/ipfs/..."
+///             ]
 ///         }
 ///     ]
 ///     "releases": {
 ///         "0.8.7": "solc-macosx-amd64-v0.8.7+commit.e28d00a7",
 ///         "0.8.6": "solc-macosx-amd64-v0.8.6+commit.11564f7e",
 ///         ...
-///     }
+///     },
+///     "latestRelease": "0.8.7"
 /// }
 /// ```
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Releases {
     pub builds: Vec<BuildInfo>,
     pub releases: BTreeMap<Version, String>,
+    #[serde(
+        default,
+        rename = "latestRelease",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub latest_release: Option<Version>,
 }
 
 impl Releases {
+    /// Get the build info of a solc version if it exists.
+    /// Checks for exact version match or for prerelease.
+    ///
+    /// If the version has build metadata, e.g. `0.8.7+commit.e28d00a7`, only a build from the same
+    /// commit matches.
+    ///
+    /// This scans all builds, use a [`ReleaseIndex`](crate::ReleaseIndex) for repeated lookups.
+    pub fn get_build_info(&self, v: &Version) -> Option<&BuildInfo> {
+        let key = build_key(v);
+        self.select_build(
+            v,
+            self.builds
+                .iter()
+                .filter(|build_info| build_info.key() == key),
+        )
+    }
+
+    /// Selects the build of a version among the builds of its numeric version and pre-release.
+    pub(crate) fn select_build<'a>(
+        &self,
+        v: &Version,
+        mut candidates: impl Iterator<Item = &'a BuildInfo>,
+    ) -> Option<&'a BuildInfo> {
+        if v.build.is_empty() {
+            return candidates.next();
+        }
+
+        // Lists which don't record the commit of a build may still have it in the artifact name.
+        let artifact_commit = self
+            .releases
+            .get(&without_build(v))
+            .and_then(|artifact| artifact_commit(artifact));
+        candidates.find(|build_info| {
+            build_info.long_version.as_deref() == Some(v.to_string().as_str())
+                || build_info.build.as_deref() == Some(v.build.as_str())
+                || metadata_commit(v.build.as_str())
+                    .is_some_and(|commit| build_info.commit().or(artifact_commit) == Some(commit))
+        })
+    }
+
     /// Get the checksum of a solc version's binary if it exists.
     /// Checks for exact version match or for prerelease.
     pub fn get_checksum(&self, v: &Version) -> Option<Vec<u8>> {
-        let matches = |build_info: &BuildInfo| {
-            build_info.version == Version::new(v.major, v.minor, v.patch)
-                && build_info
-                    .prerelease
-                    .as_deref()
-                    .unwrap_or("")
-                    .eq_ignore_ascii_case(v.pre.as_str())
-        };
-
-        self.builds
-            .iter()
-            .find(|build_info| matches(build_info))
+        self.get_build_info(v)
             .map(|build_info| build_info.sha256.clone())
     }
 
     /// Returns the artifact of the version if any, by looking it up in releases or in builds (if
     /// a prerelease).
+    ///
+    /// If the version has build metadata, e.g. `0.8.7+commit.e28d00a7`, the artifact is only
+    /// returned if it was built from the same commit.
     pub fn get_artifact(&self, version: &Version) -> Option<&String> {
+        self.find_artifact(version, &|version| self.get_build_info(version))
+    }
+
+    /// Same as [`get_artifact`](Self::get_artifact), looking up builds with `lookup`.
+    pub(crate) fn find_artifact<'a>(
+        &'a self,
+        version: &Version,
+        lookup: &dyn Fn(&Version) -> Option<&'a BuildInfo>,
+    ) -> Option<&'a String> {
+        if !version.build.is_empty() {
+            let build_info = lookup(version)?;
+            if let Some(path) = &build_info.path {
+                return Some(path);
+            }
+            let artifact = self.find_artifact(&without_build(version), lookup)?;
+            let matches = match (artifact_commit(artifact), build_info.commit()) {
+                (Some(artifact_commit), Some(commit)) => artifact_commit == commit,
+                _ => true,
+            };
+            return matches.then_some(artifact);
+        }
+
         // Check version artifact in releases.
         if let Some(artifact) = self.releases.get(version) {
             return Some(artifact);
         }
 
         // If we didn't find any artifact under releases, look up builds for prerelease.
-        if !version.pre.is_empty()
-            && let Some(build_info) = self.builds.iter().find(|b| {
-                b.version == Version::new(version.major, version.minor, version.patch)
-                    && b.prerelease == Some(version.pre.to_string())
-            })
-        {
-            return build_info.path.as_ref();
+        if !version.pre.is_empty() {
+            return lookup(version)?.path.as_ref();
         }
 
         None
     }
 
+    /// Resolves the artifact of a version for the platform these releases were fetched for.
+    pub fn resolve(&self, platform: Platform, version: &Version) -> Result<Artifact, SvmError> {
+        self.find_resolve(platform, version, &|version| self.get_build_info(version))
+    }
+
+    /// Same as [`resolve`](Self::resolve), looking up builds with `lookup`.
+    pub(crate) fn find_resolve<'a>(
+        &'a self,
+        platform: Platform,
+        version: &Version,
+        lookup: &dyn Fn(&Version) -> Option<&'a BuildInfo>,
+    ) -> Result<Artifact, SvmError> {
+        let name = self
+            .find_artifact(version, lookup)
+            .ok_or_else(|| SvmError::UnknownVersion(version.clone()))?;
+        Artifact::new(platform, version, name)
+    }
+
+    /// Indexes the releases of `platform` for lookups by version.
+    pub fn index(self, platform: Platform) -> ReleaseIndex {
+        ReleaseIndex::new(platform, self)
+    }
+
     /// Returns a sorted list of all versions
     pub fn into_versions(self) -> Vec<Version> {
         let mut versions = self.releases.into_keys().collect::<Vec<_>>();
@@ -138,10 +407,49 @@ impl Releases {
     fn extend(&mut self, other: Self) {
         self.builds.extend(other.builds);
         self.releases.extend(other.releases);
+        self.latest_release = self.latest_release.take().max(other.latest_release);
+    }
+}
+
+/// The artifact of a Solc version for a platform, and where it comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Artifact {
+    /// The version of Solc.
+    pub version: Version,
+    /// The platform the artifact is for.
+    pub platform: Platform,
+    /// The name of the artifact in the release list, e.g.
+    /// `solc-linux-amd64-v0.8.7+commit.e28d00a7`.
+    pub name: String,
+    /// The URL of the artifact on the default host of its source.
+    pub url: Url,
+    /// The source providing the artifact.
+    pub source: Source,
+    /// Whether the artifact runs natively on the platform or under emulation.
+    pub execution: Execution,
+}
+
+impl Artifact {
+    pub(crate) fn new(platform: Platform, version: &Version, name: &str) -> Result<Self, SvmError> {
+        let url = artifact_url(platform, version, name)?;
+        let source = release_source(platform, version).expect("artifact URL has a source");
+        Ok(Self {
+            version: version.clone(),
+            platform,
+            name: name.to_string(),
+            url,
+            source: source.source,
+            execution: source.execution,
+        })
+    }
+
+    /// Returns whether the artifact is an official Solidity build.
+    pub const fn trust(&self) -> Trust {
+        self.source.trust()
     }
 }
 
-/// Build info contains the SHA256 checksum of a solc binary.
+/// Build info contains the checksums and download locations of a solc binary.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BuildInfo {
     pub version: Version,
@@ -149,6 +457,87 @@ pub struct BuildInfo {
     pub sha256: Vec<u8>,
     pub path: Option<String>,
     pub prerelease: Option<String>,
+    /// The build metadata, e.g. `commit.e28d00a7`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+    /// The full version including build metadata, e.g. `0.8.7+commit.e28d00a7`.
+    #[serde(
+        default,
+        rename = "longVersion",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub long_version: Option<String>,
+    /// The Keccak-256 checksum of the binary.
+    #[serde(
+        default,
+        with = "hex_string::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub keccak256: Option<Vec<u8>>,
+    /// Content addressed locations of the binary, e.g. `dweb:/ipfs/...` and `bzzr://...`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+}
+
+impl BuildInfo {
+    /// Returns the numeric version and the lowercase pre-release the build is looked up by.
+    pub(crate) fn key(&self) -> (Version, String) {
+        (
+            self.version.clone(),
+            self.prerelease
+                .as_deref()
+                .unwrap_or_default()
+                .to_ascii_lowercase(),
+        )
+    }
+
+    /// Returns the hash of the Solidity commit the binary was built from, e.g. `e28d00a7`.
+    ///
+    /// This is read from the build metadata, the long version or the artifact path, whichever is
+    /// available.
+    pub fn commit(&self) -> Option<&str> {
+        self.build
+            .as_deref()
+            .and_then(metadata_commit)
+            .or_else(|| {
+                let (_, build) = self.long_version.as_deref()?.split_once('+')?;
+                metadata_commit(build)
+            })
+            .or_else(|| artifact_commit(self.path.as_deref()?))
+    }
+}
+
+/// Returns the key of the builds of a version, see [`BuildInfo::key`].
+pub(crate) fn build_key(version: &Version) -> (Version, String) {
+    (
+        Version::new(version.major, version.minor, version.patch),
+        version.pre.as_str().to_ascii_lowercase(),
+    )
+}
+
+/// Returns the version without build metadata, e.g. `0.8.7` for `0.8.7+commit.e28d00a7`.
+///
+/// Release lists key the builds of a release by this version.
+pub fn without_build(version: &Version) -> Version {
+    Version {
+        build: semver::BuildMetadata::EMPTY,
+        ..version.clone()
+    }
+}
+
+/// Returns the commit hash in build metadata, e.g. `commit.e28d00a7`.
+pub(crate) fn metadata_commit(build: &str) -> Option<&str> {
+    build
+        .strip_prefix("commit.")?
+        .split('.')
+        .next()
+        .filter(|commit| !commit.is_empty())
+}
+
+/// Returns the commit hash in an artifact name, e.g. `solc-linux-amd64-v0.8.10+commit.fc410830`.
+pub(crate) fn artifact_commit(artifact: &str) -> Option<&str> {
+    let (_, build) = artifact.split_once('+')?;
+    metadata_commit(build)
 }
 
 /// Helper serde module to serialize and deserialize bytes as hex.
@@ -170,162 +559,285 @@ mod hex_string {
     {
         serializer.serialize_str(&hex::encode_prefixed(value))
     }
+
+    /// Same as the parent module, for optional values.
+    pub mod option {
+        use super::*;
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|s| hex::decode(s).map_err(de::Error::custom))
+                .transpose()
+        }
+
+        pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+            T: AsRef<[u8]>,
+        {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+}
+
+/// Where a release list was read from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReleasesOrigin {
+    /// Fetched from the release sources.
+    Live,
+    /// Read from the copy cached on disk by the last successful fetch.
+    Cache,
+    /// Read from the snapshot bundled with svm, see [`snapshot_releases`].
+    Snapshot,
+}
+
+impl fmt::Display for ReleasesOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Live => "live",
+            Self::Cache => "cache",
+            Self::Snapshot => "snapshot",
+        })
+    }
+}
+
+/// Returns the release list of the platform bundled with svm, if any.
+///
+/// The snapshot is taken when svm is released, so it doesn't know about later releases, but its
+/// checksums can be used to verify artifacts without any network access.
+pub fn snapshot_releases(platform: Platform) -> Option<Releases> {
+    SNAPSHOT_RELEASES.get(&platform).cloned()
 }
 
 /// Blocking version of [`all_releases`].
 #[cfg(feature = "blocking")]
 pub fn blocking_all_releases(platform: Platform) -> Result<Releases, SvmError> {
-    match platform {
-        Platform::LinuxAarch64 => {
-            // Prior to version 0.8.31, releases for Linux arm64 builds was provided by the
-            // `nikitastupin/solc` repository. From 0.8.31 (inclusive) and onwards, official
-            // binary releases are provided by the Solidity project.
-            let mut releases =
-                reqwest::blocking::get(LINUX_AARCH64_RELEASES_URL)?.json::<Releases>()?;
-            releases.retain_versions(|v| *v < LINUX_AARCH64_BINARIES);
-            fix_build_prerelease(&mut releases);
-            let mut official = reqwest::blocking::get(format!(
-                "{SOLC_RELEASES_URL}/{LINUX_AARCH64_PLATFORM}/list.json",
-            ))?
-            .json::<Releases>()?;
-            // Filtering older releases isn't strinctly necessary here, but do it just in case
-            // Solidity retroactively starts adding older release binaries for linux-aarch64.
-            official.retain_versions(|v| *v >= LINUX_AARCH64_BINARIES);
-            releases.extend(official);
-            Ok(releases)
-        }
-        Platform::MacOsAarch64 => {
-            // The supported versions for both macos-amd64 and macos-aarch64 are the same.
-            //
-            // 1. For version >= 0.8.5 we fetch native releases from
-            // https://github.com/alloy-rs/solc-builds
-            //
-            // 2. For version <= 0.8.4 we fetch releases from https://binaries.soliditylang.org and
-            // require Rosetta support.
-            //
-            // Note: Since 0.8.24 universal macosx releases are available
-            let native = reqwest::blocking::get(MACOS_AARCH64_RELEASES_URL)?.json::<Releases>()?;
-            let mut releases = reqwest::blocking::get(format!(
-                "{}/{}/list.json",
-                SOLC_RELEASES_URL,
-                Platform::MacOsAmd64,
-            ))?
-            .json::<Releases>()?;
-            releases
-                .retain_versions(|v| *v < MACOS_AARCH64_NATIVE || *v > UNIVERSAL_MACOS_BINARIES);
-            releases.extend(native);
-            Ok(releases)
-        }
-        Platform::AndroidAarch64 => {
-            Ok(reqwest::blocking::get(ANDROID_AARCH64_RELEASES_URL)?.json::<Releases>()?)
-        }
-        Platform::WindowsAarch64 => {
-            // Windows ARM64 uses x64 binaries via emulation
-            // Solidity does not provide native ARM64 Windows binaries
-            let releases = reqwest::blocking::get(format!(
-                "{SOLC_RELEASES_URL}/{}/list.json",
-                Platform::WindowsAmd64
-            ))?
-            .json::<Releases>()?;
-            Ok(unified_releases(releases, platform))
-        }
-        _ => {
-            let releases =
-                reqwest::blocking::get(format!("{SOLC_RELEASES_URL}/{platform}/list.json"))?
-                    .json::<Releases>()?;
-            Ok(unified_releases(releases, platform))
-        }
+    Ok(blocking_all_releases_with_origin(platform)?.0)
+}
+
+/// Blocking version of [`all_releases_with_origin`].
+#[cfg(feature = "blocking")]
+pub fn blocking_all_releases_with_origin(
+    platform: Platform,
+) -> Result<(Releases, ReleasesOrigin), SvmError> {
+    blocking_releases_with_fallback(platform, &MirrorOverrides::default())
+}
+
+/// Blocking version of [`releases_with_fallback`].
+#[cfg(feature = "blocking")]
+pub(crate) fn blocking_releases_with_fallback(
+    platform: Platform,
+    overrides: &MirrorOverrides,
+) -> Result<(Releases, ReleasesOrigin), SvmError> {
+    let live = blocking_fetch_all_releases(platform, overrides);
+    with_fallback(
+        platform,
+        live,
+        SNAPSHOT_RELEASES.get(&platform),
+        overrides.is_empty(),
+    )
+}
+
+/// Blocking version of [`fetch_all_releases`].
+#[cfg(feature = "blocking")]
+fn blocking_fetch_all_releases(
+    platform: Platform,
+    overrides: &MirrorOverrides,
+) -> Result<Releases, SvmError> {
+    let mut lists = Vec::new();
+    for url in list_urls(platform) {
+        let list = blocking_get_releases(&url, overrides)?;
+        lists.push((url, list));
     }
+    merge_releases(platform, &lists)
 }
 
 /// Fetch all releases available for the provided platform.
+///
+/// If the release sources are unavailable, the list cached by the last successful fetch or the
+/// bundled snapshot is returned instead, see [`all_releases_with_origin`].
 pub async fn all_releases(platform: Platform) -> Result<Releases, SvmError> {
-    match platform {
-        Platform::LinuxAarch64 => {
-            // Prior to version 0.8.31, releases for Linux arm64 builds was provided by the
-            // `nikitastupin/solc` repository. From 0.8.31 (inclusive) and onwards, official
-            // binary releases are provided by the Solidity project.
-            let mut releases = get(LINUX_AARCH64_RELEASES_URL)
-                .await?
-                .json::<Releases>()
-                .await?;
-            releases.retain_versions(|v| *v < LINUX_AARCH64_BINARIES);
-            fix_build_prerelease(&mut releases);
-            let mut official = get(format!(
-                "{SOLC_RELEASES_URL}/{LINUX_AARCH64_PLATFORM}/list.json",
-            ))
-            .await?
-            .json::<Releases>()
-            .await?;
-            // Filtering older releases isn't strinctly necessary here, but do it just in case
-            // Solidity retroactively starts adding older release binaries for linux-aarch64.
-            official.retain_versions(|v| *v >= LINUX_AARCH64_BINARIES);
-            releases.extend(official);
-            Ok(releases)
-        }
-        Platform::MacOsAarch64 => {
-            // The supported versions for both macos-amd64 and macos-aarch64 are the same.
-            //
-            // 1. For version >= 0.8.5 we fetch native releases from
-            // https://github.com/alloy-rs/solc-builds
-            //
-            // 2. For version <= 0.8.4 we fetch releases from https://binaries.soliditylang.org and
-            // require Rosetta support.
-            let native = get(MACOS_AARCH64_RELEASES_URL)
-                .await?
-                .json::<Releases>()
-                .await?;
-            let mut releases = get(format!(
-                "{}/{}/list.json",
-                SOLC_RELEASES_URL,
-                Platform::MacOsAmd64,
-            ))
-            .await?
-            .json::<Releases>()
-            .await?;
-            releases
-                .retain_versions(|v| *v < MACOS_AARCH64_NATIVE || *v > UNIVERSAL_MACOS_BINARIES);
+    Ok(all_releases_with_origin(platform).await?.0)
+}
 
-            releases.extend(native);
-            Ok(releases)
+/// Same as [`all_releases`], but also returns whether the list was fetched from the release
+/// sources, read from the disk cache or from the bundled snapshot.
+pub async fn all_releases_with_origin(
+    platform: Platform,
+) -> Result<(Releases, ReleasesOrigin), SvmError> {
+    releases_with_fallback(platform, &MirrorOverrides::default()).await
+}
+
+/// Same as [`all_releases_with_origin`], but fetches the release lists from the overridden
+/// mirrors of their sources.
+pub(crate) async fn releases_with_fallback(
+    platform: Platform,
+    overrides: &MirrorOverrides,
+) -> Result<(Releases, ReleasesOrigin), SvmError> {
+    let live = fetch_all_releases(platform, overrides).await;
+    with_fallback(
+        platform,
+        live,
+        SNAPSHOT_RELEASES.get(&platform),
+        overrides.is_empty(),
+    )
+}
+
+/// Caches a successfully fetched release list if `cache_live` is set, or falls back to the cached
+/// list or `snapshot` if the release sources are unavailable.
+///
+/// Lists fetched from overridden mirrors aren't cached, so that they can't replace the list of the
+/// default mirrors. Any other error, e.g. an invalid signature, is returned as is.
+fn with_fallback(
+    platform: Platform,
+    live: Result<Releases, SvmError>,
+    snapshot: Option<&Releases>,
+    cache_live: bool,
+) -> Result<(Releases, ReleasesOrigin), SvmError> {
+    let err = match live {
+        Ok(releases) => {
+            // The cache is best-effort, failing to write it doesn't fail the fetch.
+            if cache_live {
+                let _ = write_cached_releases(platform, &releases);
+            }
+            return Ok((releases, ReleasesOrigin::Live));
         }
-        Platform::AndroidAarch64 => Ok(get(ANDROID_AARCH64_RELEASES_URL)
-            .await?
-            .json::<Releases>()
-            .await?),
-        Platform::WindowsAarch64 => {
-            // Windows ARM64 uses x64 binaries via emulation
-            // Solidity does not provide native ARM64 Windows binaries
-            let releases = get(format!(
-                "{SOLC_RELEASES_URL}/{}/list.json",
-                Platform::WindowsAmd64
-            ))
-            .await?
-            .json::<Releases>()
-            .await?;
+        Err(err) if mirrors::is_unavailable(&err) => err,
+        Err(err) => return Err(err),
+    };
+    if let Some(releases) = read_cached_releases(platform) {
+        return Ok((releases, ReleasesOrigin::Cache));
+    }
+    match snapshot {
+        Some(releases) => Ok((releases.clone(), ReleasesOrigin::Snapshot)),
+        None => Err(err),
+    }
+}
+
+fn write_cached_releases(platform: Platform, releases: &Releases) -> Result<(), SvmError> {
+    let path = releases_cache_path(platform);
+    fs::create_dir_all(path.parent().expect("cache path has a parent"))?;
+    fs::write(path, serde_json::to_vec(releases)?)?;
+    Ok(())
+}
+
+fn read_cached_releases(platform: Platform) -> Option<Releases> {
+    serde_json::from_slice(&fs::read(releases_cache_path(platform)).ok()?).ok()
+}
+
+/// Fetches the release lists of the platform from the overridden mirrors of their sources and
+/// merges them.
+async fn fetch_all_releases(
+    platform: Platform,
+    overrides: &MirrorOverrides,
+) -> Result<Releases, SvmError> {
+    let mut lists = Vec::new();
+    for url in list_urls(platform) {
+        let list = get_releases(&url, overrides).await?;
+        lists.push((url, list));
+    }
+    merge_releases(platform, &lists)
+}
 
-            Ok(unified_releases(releases, platform))
+/// Returns the URLs of the release lists to fetch for the platform's release sources.
+///
+/// Sources may serve different ranges of the same list, which is only fetched once.
+fn list_urls(platform: Platform) -> Vec<String> {
+    let mut urls = Vec::new();
+    for url in release_sources(platform)
+        .iter()
+        .filter_map(ReleaseSource::list_url)
+    {
+        if !urls.contains(&url) {
+            urls.push(url);
         }
-        _ => {
-            let releases = get(format!("{SOLC_RELEASES_URL}/{platform}/list.json"))
-                .await?
-                .json::<Releases>()
-                .await?;
+    }
+    urls
+}
 
-            Ok(unified_releases(releases, platform))
+/// Merges the release lists fetched from the platform's release sources, given with their URL,
+/// keeping each version from the source serving it.
+///
+/// The lists of bundled sources are taken from the crate rather than `lists`. In strict mode,
+/// each fetched list is validated before it is merged.
+fn merge_releases(platform: Platform, lists: &[(String, Releases)]) -> Result<Releases, SvmError> {
+    let strict = validation::is_strict();
+    let sources = release_sources(platform);
+    let mut merged = Releases::default();
+    for (index, source) in sources.iter().enumerate() {
+        let url = source.list_url();
+        let mut list = match source.bundled {
+            Some(bundled) => Releases::clone(bundled),
+            None => lists
+                .iter()
+                .find(|(fetched, _)| Some(fetched) == url.as_ref())
+                .map(|(_, list)| list.clone())
+                .unwrap_or_default(),
+        };
+        if strict && let Some(url) = &url {
+            ensure_valid(url, &list, source.unlabelled_prereleases)?;
+        }
+        if source.unlabelled_prereleases {
+            fix_build_prerelease(&mut list);
         }
+        list.retain_versions(|version| {
+            sources
+                .iter()
+                .position(|source| source.versions.contains(version))
+                == Some(index)
+        });
+        merged.extend(list);
     }
+    Ok(merged)
 }
 
-/// unifies the releases with old releases if on linux
-fn unified_releases(releases: Releases, platform: Platform) -> Releases {
-    if platform == Platform::LinuxAmd64 {
-        let mut all_releases = OLD_SOLC_RELEASES.clone();
-        all_releases.extend(releases);
-        all_releases
-    } else {
-        releases
+/// Blocking version of [`get_releases`]
+#[cfg(feature = "blocking")]
+fn blocking_get_releases(url: &str, overrides: &MirrorOverrides) -> Result<Releases, SvmError> {
+    let urls = overrides.urls(url)?;
+    let policy = SignaturePolicy::from_env()?;
+    let client = reqwest::blocking::Client::new();
+    let (url, res) = mirrors::blocking_get(&client, &urls)?;
+    let list = res.bytes()?;
+    if policy.is_enabled() {
+        let signature = match mirrors::blocking_get(&client, &[signature_url(&url)]) {
+            Ok((_, res)) => Some(res.bytes()?),
+            Err(SvmError::UnsuccessfulResponse(_, StatusCode::NOT_FOUND)) => None,
+            Err(err) => return Err(err),
+        };
+        policy.verify(&url, &list, signature.as_deref())?;
     }
+    Ok(serde_json::from_slice(&list)?)
+}
+
+/// Fetches the release list at `url`, from the first mirror of its source that serves it.
+///
+/// The list is only accepted if its signature checks out, see [`SVM_RELEASES_PUBLIC_KEYS`].
+///
+/// [`SVM_RELEASES_PUBLIC_KEYS`]: crate::SVM_RELEASES_PUBLIC_KEYS
+async fn get_releases(url: &str, overrides: &MirrorOverrides) -> Result<Releases, SvmError> {
+    fetch_releases(&overrides.urls(url)?, &SignaturePolicy::from_env()?).await
+}
+
+async fn fetch_releases(urls: &[Url], policy: &SignaturePolicy) -> Result<Releases, SvmError> {
+    let client = reqwest::Client::new();
+    let (url, res) = mirrors::get(&client, urls).await?;
+    let list = res.bytes().await?;
+    if policy.is_enabled() {
+        let signature = match mirrors::get(&client, &[signature_url(&url)]).await {
+            Ok((_, res)) => Some(res.bytes().await?),
+            Err(SvmError::UnsuccessfulResponse(_, StatusCode::NOT_FOUND)) => None,
+            Err(err) => return Err(err),
+        };
+        policy.verify(&url, &list, signature.as_deref())?;
+    }
+    Ok(serde_json::from_slice(&list)?)
 }
 
 /// Fixes build pre-release info for certain release lists.
@@ -343,91 +855,20 @@ fn fix_build_prerelease(releases: &mut Releases) {
 }
 
 /// Construct the URL to the Solc binary for the specified release version and target platform.
-pub(crate) fn artifact_url(
+pub fn artifact_url(
     platform: Platform,
     version: &Version,
     artifact: &str,
 ) -> Result<Url, SvmError> {
-    if platform == Platform::LinuxAmd64
-        && *version <= OLD_VERSION_MAX
-        && *version >= OLD_VERSION_MIN
-    {
-        return Ok(Url::parse(&format!(
-            "{OLD_SOLC_RELEASES_DOWNLOAD_PREFIX}/{artifact}"
-        ))?);
-    }
-
-    if platform == Platform::LinuxAarch64 {
-        if *version >= LINUX_AARCH64_BINARIES {
-            return Ok(Url::parse(&format!(
-                "{SOLC_RELEASES_URL}/{LINUX_AARCH64_PLATFORM}/{artifact}"
-            ))?);
-        } else if *version >= LINUX_AARCH64_MIN {
-            return Ok(Url::parse(&format!(
-                "{LINUX_AARCH64_URL_PREFIX}/{artifact}"
-            ))?);
-        } else {
-            return Err(SvmError::UnsupportedVersion(
-                version.to_string(),
-                platform.to_string(),
-            ));
-        }
-    }
-
-    if platform == Platform::MacOsAmd64 && *version < OLD_VERSION_MIN {
-        return Err(SvmError::UnsupportedVersion(
-            version.to_string(),
-            platform.to_string(),
-        ));
-    }
-
-    if platform == Platform::MacOsAarch64 {
-        if *version >= MACOS_AARCH64_NATIVE && *version <= UNIVERSAL_MACOS_BINARIES {
-            // fetch natively build solc binaries from `https://github.com/alloy-rs/solc-builds`
-            return Ok(Url::parse(&format!(
-                "{MACOS_AARCH64_URL_PREFIX}/{artifact}"
-            ))?);
-        } else {
-            // if version is older or universal macos binaries are available, fetch from `https://binaries.soliditylang.org`
-            return Ok(Url::parse(&format!(
-                "{}/{}/{}",
-                SOLC_RELEASES_URL,
-                Platform::MacOsAmd64,
-                artifact,
-            ))?);
-        }
-    }
-
-    if platform == Platform::AndroidAarch64 {
-        if version.ge(&ANDROID_AARCH64_MIN) {
-            return Ok(Url::parse(&format!(
-                "{ANDROID_AARCH64_URL_PREFIX}/{artifact}"
-            ))?);
-        } else {
-            return Err(SvmError::UnsupportedVersion(
-                version.to_string(),
-                platform.to_string(),
-            ));
-        }
-    }
-
-    if platform == Platform::WindowsAarch64 {
-        // Windows ARM64 uses x64 binaries via emulation
-        // Solidity does not provide native ARM64 Windows binaries
-        return Ok(Url::parse(&format!(
-            "{SOLC_RELEASES_URL}/{}/{artifact}",
-            Platform::WindowsAmd64,
-        ))?);
-    }
-
-    Ok(Url::parse(&format!(
-        "{SOLC_RELEASES_URL}/{platform}/{artifact}"
-    ))?)
+    release_source(platform, version)
+        .ok_or_else(|| SvmError::UnsupportedVersion(version.to_string(), platform.to_string()))?
+        .artifact_url(artifact)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::paths::tests::TempDataDir;
 
     #[test]
     fn test_artifact_url() {
@@ -442,6 +883,378 @@ mod tests {
         )
     }
 
+    #[test]
+    fn release_source_urls() {
+        let url = |platform: Platform, version: &str| {
+            artifact_url(platform, &Version::parse(version).unwrap(), "solc")
+                .map(|url| url.to_string())
+        };
+
+        assert_eq!(
+            url(Platform::LinuxAmd64, "0.4.9").unwrap(),
+            "https://raw.githubusercontent.com/crytic/solc/master/linux/amd64/solc"
+        );
+        assert_eq!(
+            url(Platform::LinuxAmd64, "0.4.10").unwrap(),
+            "https://binaries.soliditylang.org/linux-amd64/solc"
+        );
+
+        assert!(matches!(
+            url(Platform::LinuxAarch64, "0.4.26"),
+            Err(SvmError::UnsupportedVersion(..))
+        ));
+        assert!(
+            url(Platform::LinuxAarch64, "0.8.31-pre.1")
+                .unwrap()
+                .starts_with(Source::Nikitastupin.url())
+        );
+        assert_eq!(
+            url(Platform::LinuxAarch64, "0.8.31").unwrap(),
+            "https://binaries.soliditylang.org/linux-arm64/solc"
+        );
+
+        assert!(matches!(
+            url(Platform::MacOsAmd64, "0.3.6"),
+            Err(SvmError::UnsupportedVersion(..))
+        ));
+        assert_eq!(
+            url(Platform::MacOsAarch64, "0.8.4").unwrap(),
+            "https://binaries.soliditylang.org/macosx-amd64/solc"
+        );
+        for version in ["0.8.5", "0.8.24"] {
+            assert!(
+                url(Platform::MacOsAarch64, version)
+                    .unwrap()
+                    .starts_with(Source::SolcBuilds.url())
+            );
+        }
+        assert_eq!(
+            url(Platform::MacOsAarch64, "0.8.25").unwrap(),
+            "https://binaries.soliditylang.org/macosx-amd64/solc"
+        );
+
+        assert_eq!(
+            url(Platform::WindowsAarch64, "0.8.30").unwrap(),
+            "https://binaries.soliditylang.org/windows-amd64/solc"
+        );
+        assert!(matches!(
+            url(Platform::AndroidAarch64, "0.8.23"),
+            Err(SvmError::UnsupportedVersion(..))
+        ));
+        assert!(matches!(
+            url(Platform::Unsupported, "0.8.30"),
+            Err(SvmError::UnsupportedVersion(..))
+        ));
+    }
+
+    #[test]
+    fn artifact_executions() {
+        let execution = |platform, version: &str| {
+            artifact_execution(platform, &Version::parse(version).unwrap())
+        };
+        assert_eq!(
+            execution(Platform::MacOsAarch64, "0.8.4"),
+            Some(Execution::Emulated)
+        );
+        assert_eq!(
+            execution(Platform::MacOsAarch64, "0.8.5"),
+            Some(Execution::Native)
+        );
+        assert_eq!(
+            execution(Platform::MacOsAarch64, "0.8.25"),
+            Some(Execution::Native)
+        );
+        assert_eq!(
+            execution(Platform::WindowsAarch64, "0.8.30"),
+            Some(Execution::Emulated)
+        );
+        assert_eq!(
+            execution(Platform::LinuxAarch64, "0.8.30"),
+            Some(Execution::Native)
+        );
+        assert_eq!(execution(Platform::LinuxAarch64, "0.4.26"), None);
+    }
+
+    #[test]
+    fn resolve_artifact() {
+        let json = r#"{
+            "builds": [],
+            "releases": {
+                "0.8.4": "solc-macosx-amd64-v0.8.4+commit.c7e474f2",
+                "0.8.20": "solc-macosx-aarch64-v0.8.20+commit.a1b79de6"
+            }
+        }"#;
+        let releases: Releases = serde_json::from_str(json).unwrap();
+
+        let artifact = releases
+            .resolve(Platform::MacOsAarch64, &Version::new(0, 8, 20))
+            .unwrap();
+        assert_eq!(artifact.name, "solc-macosx-aarch64-v0.8.20+commit.a1b79de6");
+        assert_eq!(artifact.source, Source::SolcBuilds);
+        assert_eq!(artifact.trust(), Trust::Community);
+        assert!(artifact.url.as_str().starts_with(Source::SolcBuilds.url()));
+
+        assert_eq!(artifact.execution, Execution::Native);
+
+        let artifact = releases
+            .resolve(Platform::MacOsAarch64, &Version::new(0, 8, 4))
+            .unwrap();
+        assert_eq!(artifact.source, Source::Official);
+        assert_eq!(artifact.trust(), Trust::Official);
+        assert_eq!(artifact.execution, Execution::Emulated);
+
+        assert!(matches!(
+            releases.resolve(Platform::MacOsAarch64, &Version::new(0, 8, 21)),
+            Err(SvmError::UnknownVersion(_))
+        ));
+    }
+
+    #[test]
+    fn merge_release_lists() {
+        let list = |versions: &[&str], name: &str| Releases {
+            builds: versions
+                .iter()
+                .map(|version| BuildInfo {
+                    version: Version::parse(version).unwrap(),
+                    sha256: vec![],
+                    path: None,
+                    prerelease: None,
+                    build: None,
+                    long_version: None,
+                    keccak256: None,
+                    urls: vec![],
+                })
+                .collect(),
+            releases: versions
+                .iter()
+                .map(|version| {
+                    (
+                        Version::parse(version).unwrap(),
+                        format!("{name}-{version}"),
+                    )
+                })
+                .collect(),
+            latest_release: None,
+        };
+        let list_url =
+            |platform, index: usize| release_sources(platform)[index].list_url().unwrap();
+        let native = list(&["0.8.5", "0.8.24", "0.8.25"], "native");
+        let official = list(&["0.8.4", "0.8.5", "0.8.25"], "official");
+
+        // The official list is fetched once for both of the sources serving it.
+        assert_eq!(
+            list_urls(Platform::MacOsAarch64),
+            [
+                list_url(Platform::MacOsAarch64, 0),
+                list_url(Platform::MacOsAarch64, 1)
+            ]
+        );
+        let merged = merge_releases(
+            Platform::MacOsAarch64,
+            &[
+                (list_url(Platform::MacOsAarch64, 0), native),
+                (list_url(Platform::MacOsAarch64, 1), official),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            merged
+                .releases
+                .values()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+            [
+                "official-0.8.4",
+                "native-0.8.5",
+                "native-0.8.24",
+                "official-0.8.25"
+            ]
+        );
+        assert_eq!(merged.builds.len(), 4);
+
+        // macOS versions before 0.4.0 are unsupported.
+        let official = list(&["0.3.6", "0.4.0", "0.8.25"], "official");
+        let merged = merge_releases(
+            Platform::MacOsAmd64,
+            &[(list_url(Platform::MacOsAmd64, 0), official.clone())],
+        )
+        .unwrap();
+        assert_eq!(
+            merged.into_versions(),
+            ["0.4.0", "0.8.25"].map(|v| Version::parse(v).unwrap())
+        );
+        assert!(matches!(
+            official.resolve(Platform::MacOsAmd64, &Version::new(0, 3, 6)),
+            Err(SvmError::UnsupportedVersion(..))
+        ));
+
+        // Bundled lists are merged in even though they aren't fetched.
+        let official = list(&["0.4.10"], "official");
+        assert_eq!(
+            list_urls(Platform::LinuxAmd64),
+            [list_url(Platform::LinuxAmd64, 1)]
+        );
+        let merged = merge_releases(
+            Platform::LinuxAmd64,
+            &[(list_url(Platform::LinuxAmd64, 1), official)],
+        )
+        .unwrap();
+        assert!(merged.releases.contains_key(&OLD_VERSION_MIN));
+        assert!(merged.releases.contains_key(&Version::new(0, 4, 10)));
+    }
+
+    #[test]
+    fn full_list_schema_roundtrip() {
+        let json = r#"{
+            "builds": [
+                {
+                    "path": "solc-linux-amd64-v0.8.7+commit.e28d00a7",
+                    "version": "0.8.7",
+                    "build": "commit.e28d00a7",
+                    "longVersion": "0.8.7+commit.e28d00a7",
+                    "keccak256": "0x1111111111111111111111111111111111111111111111111111111111111111",
+                    "sha256": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    "urls": [
+                        "bzzr://3333333333333333333333333333333333333333333333333333333333333333",
+                        "dweb:/ipfs/QmTvdFCHRr86vc7mH9HZ5jH7oLvXA3NtR2SXZLTJFUZ3Cb"
+                    ]
+                }
+            ],
+            "releases": {
+                "0.8.7": "solc-linux-amd64-v0.8.7+commit.e28d00a7"
+            },
+            "latestRelease": "0.8.7"
+        }"#;
+        let releases: Releases = serde_json::from_str(json).unwrap();
+        assert_eq!(releases.latest_release, Some(Version::new(0, 8, 7)));
+
+        let build = releases.get_build_info(&Version::new(0, 8, 7)).unwrap();
+        assert_eq!(build.keccak256.as_deref(), Some(&[0x11; 32][..]));
+        assert_eq!(build.long_version.as_deref(), Some("0.8.7+commit.e28d00a7"));
+        assert_eq!(build.urls.len(), 2);
+        assert_eq!(build.commit(), Some("e28d00a7"));
+
+        let s = serde_json::to_string(&releases).unwrap();
+        assert!(s.contains(r#""longVersion":"0.8.7+commit.e28d00a7""#));
+        assert!(s.contains(r#""keccak256":"0x1111"#));
+        assert_eq!(serde_json::from_str::<Releases>(&s).unwrap(), releases);
+
+        // Lists without the optional fields still round-trip without them.
+        let s = serde_json::to_string(&*OLD_SOLC_RELEASES).unwrap();
+        assert!(!s.contains("keccak256") && !s.contains("urls") && !s.contains("latestRelease"));
+        assert_eq!(OLD_SOLC_RELEASES.builds[0].commit(), None);
+    }
+
+    #[test]
+    fn test_artifact_commit() {
+        assert_eq!(
+            artifact_commit("solc-linux-amd64-v0.8.10+commit.fc410830"),
+            Some("fc410830")
+        );
+        assert_eq!(
+            artifact_commit("solc-windows-amd64-v0.7.1+commit.f4a555be.zip"),
+            Some("f4a555be")
+        );
+        assert_eq!(artifact_commit("solc-v0.8.5"), None);
+    }
+
+    #[test]
+    fn long_version_lookup() {
+        let json = r#"{
+            "builds": [
+                {
+                    "path": "solc-linux-amd64-v0.8.7+commit.e28d00a7",
+                    "version": "0.8.7",
+                    "longVersion": "0.8.7+commit.e28d00a7",
+                    "sha256": "0x11"
+                },
+                {
+                    "path": "solc-linux-amd64-v0.8.7+commit.0123abcd",
+                    "version": "0.8.7",
+                    "longVersion": "0.8.7+commit.0123abcd",
+                    "sha256": "0x22"
+                },
+                {
+                    "version": "0.8.6",
+                    "sha256": "0x33"
+                }
+            ],
+            "releases": {
+                "0.8.7": "solc-linux-amd64-v0.8.7+commit.e28d00a7",
+                "0.8.6": "solc-linux-amd64-v0.8.6+commit.11564f7e"
+            }
+        }"#;
+        let releases: Releases = serde_json::from_str(json).unwrap();
+        let v = |s: &str| Version::parse(s).unwrap();
+
+        assert_eq!(releases.get_checksum(&v("0.8.7")), Some(vec![0x11]));
+        assert_eq!(
+            releases.get_checksum(&v("0.8.7+commit.e28d00a7")),
+            Some(vec![0x11])
+        );
+        assert_eq!(
+            releases.get_checksum(&v("0.8.7+commit.0123abcd")),
+            Some(vec![0x22])
+        );
+        assert_eq!(
+            releases
+                .get_artifact(&v("0.8.7+commit.0123abcd"))
+                .map(String::as_str),
+            Some("solc-linux-amd64-v0.8.7+commit.0123abcd")
+        );
+        assert_eq!(releases.get_artifact(&v("0.8.7+commit.deadbeef")), None);
+        assert_eq!(releases.get_checksum(&v("0.8.7+commit.deadbeef")), None);
+
+        // The commit is only recorded in the artifact name.
+        assert_eq!(
+            releases
+                .get_artifact(&v("0.8.6+commit.11564f7e"))
+                .map(String::as_str),
+            Some("solc-linux-amd64-v0.8.6+commit.11564f7e")
+        );
+        assert_eq!(
+            releases.get_checksum(&v("0.8.6+commit.11564f7e")),
+            Some(vec![0x33])
+        );
+        assert_eq!(releases.get_artifact(&v("0.8.6+commit.deadbeef")), None);
+    }
+
+    #[tokio::test]
+    async fn signed_release_list() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let list =
+            br#"{"builds":[],"releases":{"0.8.7":"solc-linux-amd64-v0.8.7+commit.e28d00a7"}}"#;
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signature = key.sign(list).to_bytes().to_vec();
+        let signed = crate::install::tests::serve(vec![
+            ("/list.json", 200, list.to_vec()),
+            ("/list.json.sig", 200, signature),
+        ]);
+        let unsigned = crate::install::tests::serve(vec![("/list.json", 200, list.to_vec())]);
+        let urls = |host: &Url| vec![host.join("list.json").unwrap()];
+        let public_key = hex::encode(key.verifying_key().as_bytes());
+
+        let policy = SignaturePolicy::default();
+        let releases = fetch_releases(&urls(&signed), &policy).await.unwrap();
+        assert_eq!(releases.releases.len(), 1);
+
+        let policy = SignaturePolicy::new(&public_key, None).unwrap();
+        fetch_releases(&urls(&signed), &policy).await.unwrap();
+        assert!(matches!(
+            fetch_releases(&urls(&unsigned), &policy).await,
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+
+        let other_key = hex::encode(SigningKey::from_bytes(&[8; 32]).verifying_key().as_bytes());
+        let policy = SignaturePolicy::new(&other_key, Some(false)).unwrap();
+        fetch_releases(&urls(&unsigned), &policy).await.unwrap();
+        assert!(matches!(
+            fetch_releases(&urls(&signed), &policy).await,
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+    }
+
     #[test]
     fn test_old_releases_deser() {
         assert_eq!(OLD_SOLC_RELEASES.releases.len(), 10);
@@ -467,8 +1280,8 @@ mod tests {
             releases.get_artifact(&native).unwrap(),
         )
         .expect("could not fetch artifact URL");
-        assert!(url1.to_string().contains(SOLC_RELEASES_URL));
-        assert!(url2.to_string().contains(MACOS_AARCH64_URL_PREFIX));
+        assert!(url1.to_string().contains(Source::Official.url()));
+        assert!(url2.to_string().contains(Source::SolcBuilds.url()));
     }
 
     #[tokio::test]
@@ -512,9 +1325,9 @@ mod tests {
             releases.get_artifact(&official).unwrap(),
         )
         .expect("could not fetch artifact URL");
-        assert!(url1.to_string().contains(LINUX_AARCH64_URL_PREFIX));
-        assert!(url2.to_string().contains(LINUX_AARCH64_URL_PREFIX));
-        assert!(url3.to_string().contains(SOLC_RELEASES_URL));
+        assert!(url1.to_string().contains(Source::Nikitastupin.url()));
+        assert!(url2.to_string().contains(Source::Nikitastupin.url()));
+        assert!(url3.to_string().contains(Source::Official.url()));
     }
 
     #[tokio::test]
@@ -536,4 +1349,100 @@ mod tests {
         let de_releases: Releases = serde_json::from_str(&s).unwrap();
         assert_eq!(releases, de_releases);
     }
+
+    #[test]
+    fn release_list_fallback() {
+        let _data_dir = TempDataDir::new();
+        let platform = Platform::AndroidAarch64;
+        let list = |version: &str| Releases {
+            releases: BTreeMap::from([(version.parse().unwrap(), format!("solc-v{version}"))]),
+            ..Default::default()
+        };
+        let unavailable = || SvmError::AllMirrorsFailed("connection refused".to_string());
+
+        // Without a cache or snapshot, the error is returned.
+        assert!(matches!(
+            with_fallback(platform, Err(unavailable()), None, true),
+            Err(SvmError::AllMirrorsFailed(_))
+        ));
+        let snapshot = list("0.8.24");
+        assert_eq!(
+            with_fallback(platform, Err(unavailable()), Some(&snapshot), true).unwrap(),
+            (snapshot.clone(), ReleasesOrigin::Snapshot)
+        );
+
+        // Lists fetched from overridden mirrors aren't cached.
+        let mirrored = list("0.8.26");
+        assert_eq!(
+            with_fallback(platform, Ok(mirrored.clone()), Some(&snapshot), false).unwrap(),
+            (mirrored, ReleasesOrigin::Live)
+        );
+        assert_eq!(
+            with_fallback(platform, Err(unavailable()), Some(&snapshot), true).unwrap(),
+            (snapshot.clone(), ReleasesOrigin::Snapshot)
+        );
+
+        // Live lists are cached, and preferred over the snapshot.
+        let live = list("0.8.25");
+        assert_eq!(
+            with_fallback(platform, Ok(live.clone()), Some(&snapshot), true).unwrap(),
+            (live.clone(), ReleasesOrigin::Live)
+        );
+        assert_eq!(
+            with_fallback(platform, Err(unavailable()), Some(&snapshot), true).unwrap(),
+            (live, ReleasesOrigin::Cache)
+        );
+
+        // Other errors don't fall back.
+        let invalid = SvmError::SignatureVerificationFailed {
+            url: "https://example.com/list.json".to_string(),
+            reason: "malformed signature".to_string(),
+        };
+        assert!(matches!(
+            with_fallback(platform, Err(invalid), Some(&snapshot), true),
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn snapshot_is_valid() {
+        for (platform, releases) in SNAPSHOT_RELEASES.iter() {
+            for version in releases.releases.keys() {
+                assert!(
+                    releases.get_checksum(version).is_some(),
+                    "{platform} {version}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "the bundled snapshot is a placeholder until regenerated with `update_snapshot`"]
+    fn snapshot_covers_all_platforms() {
+        for platform in Platform::ALL {
+            let releases = SNAPSHOT_RELEASES
+                .get(&platform)
+                .unwrap_or_else(|| panic!("{platform} is missing from the snapshot"));
+            assert!(!releases.releases.is_empty(), "{platform}");
+        }
+    }
+
+    /// Regenerates the bundled `list/snapshot.json` from the release sources.
+    #[tokio::test]
+    #[ignore = "fetches the release lists of every platform"]
+    async fn update_snapshot() {
+        let mut snapshot = BTreeMap::new();
+        for platform in Platform::ALL {
+            let releases = fetch_all_releases(platform, &MirrorOverrides::default())
+                .await
+                .unwrap();
+            snapshot.insert(platform, releases);
+        }
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/list/snapshot.json");
+        fs::write(
+            path,
+            serde_json::to_string_pretty(&snapshot).unwrap() + "\n",
+        )
+        .unwrap();
+    }
 }
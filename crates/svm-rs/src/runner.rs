@@ -0,0 +1,368 @@
+use crate::{SvmError, find_version_binary};
+use semver::Version;
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often a running child is checked for exit and exceeded limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long to wait for the output of a killed child, whose pipes may be held open by its own
+/// children.
+const KILLED_OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+/// Runs a Solc binary with a wall-clock timeout, a memory limit and output size caps.
+///
+/// A child exceeding a limit is killed and reaped before the error is returned:
+/// - [`SvmError::Timeout`] once the timeout elapses,
+/// - [`SvmError::OutputLimitExceeded`] once stdout or stderr grows past the output limit,
+/// - [`SvmError::MemoryLimitExceeded`] if it fails to allocate memory past the memory limit.
+///
+/// The memory limit caps the address space of the child and is only enforced on Linux.
+#[derive(Clone, Debug)]
+pub struct Runner {
+    solc_path: PathBuf,
+    timeout: Option<Duration>,
+    memory_limit: Option<u64>,
+    output_limit: Option<usize>,
+}
+
+/// The output of a Solc binary run by a [`Runner`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl Runner {
+    /// Creates a runner for the Solc binary at `solc_path`, without any limit.
+    pub fn new(solc_path: impl Into<PathBuf>) -> Self {
+        Self {
+            solc_path: solc_path.into(),
+            timeout: None,
+            memory_limit: None,
+            output_limit: None,
+        }
+    }
+
+    /// Creates a runner for an installed Solc version, without any limit.
+    pub fn for_version(version: &Version) -> Result<Self, SvmError> {
+        Ok(Self::new(find_version_binary(version)?))
+    }
+
+    /// Kills the binary if it runs for longer than `timeout`.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limits the address space of the binary to `bytes`, on Linux.
+    pub const fn memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Kills the binary if it writes more than `bytes` to stdout or stderr.
+    pub const fn output_limit(mut self, bytes: usize) -> Self {
+        self.output_limit = Some(bytes);
+        self
+    }
+
+    /// Returns the path to the binary.
+    pub fn solc_path(&self) -> &Path {
+        &self.solc_path
+    }
+
+    /// Runs the binary with `args`, writing `stdin` to its standard input.
+    ///
+    /// A non-zero exit status is not an error, unless it is caused by the memory limit.
+    pub fn run<I, S>(&self, args: I, stdin: &[u8]) -> Result<RunOutput, SvmError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut command = Command::new(&self.solc_path);
+        command
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = self.memory_limit {
+            limit_address_space(&mut command, bytes);
+        }
+        let mut child = command.spawn()?;
+
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let stdout = self.read_capped(child.stdout.take(), &exceeded);
+        let stderr = self.read_capped(child.stderr.take(), &exceeded);
+        let writer = child.stdin.take().map(|mut child_stdin| {
+            let stdin = stdin.to_vec();
+            // A child exiting without reading its input closes the pipe, which is not an error.
+            thread::spawn(move || {
+                let _ = child_stdin.write_all(&stdin);
+            })
+        });
+
+        let status = self.wait(&mut child, &exceeded);
+        let killed = status.is_err() || exceeded.load(Ordering::Relaxed);
+        // The writer of a killed child is left behind, it may be blocked on a grandchild.
+        if let Some(writer) = writer
+            && !killed
+        {
+            let _ = writer.join();
+        }
+        let stdout = collect(&stdout, killed)?;
+        let stderr = collect(&stderr, killed)?;
+        let status = status?;
+
+        if exceeded.load(Ordering::Relaxed) {
+            return Err(SvmError::OutputLimitExceeded(
+                self.name(),
+                self.output_limit.unwrap_or_default(),
+            ));
+        }
+        if let Some(bytes) = self.memory_limit
+            && cfg!(target_os = "linux")
+            && is_out_of_memory(status, &stderr)
+        {
+            return Err(SvmError::MemoryLimitExceeded(self.name(), bytes));
+        }
+        Ok(RunOutput {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Waits for the child to exit, killing it once the timeout elapses or the output limit is
+    /// exceeded.
+    fn wait(&self, child: &mut Child, exceeded: &AtomicBool) -> Result<ExitStatus, SvmError> {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+            if exceeded.load(Ordering::Relaxed) {
+                return kill(child);
+            }
+            if let Some(deadline) = deadline
+                && Instant::now() >= deadline
+            {
+                kill(child)?;
+                // Round up, so that a sub-second timeout isn't reported as 0 seconds.
+                let timeout = self.timeout.unwrap_or_default();
+                let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+                return Err(SvmError::Timeout(self.name(), secs));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Reads a pipe of the child on a separate thread, up to the output limit.
+    ///
+    /// The output is sent once the pipe is closed, see [`collect`].
+    fn read_capped(
+        &self,
+        pipe: Option<impl Read + Send + 'static>,
+        exceeded: &Arc<AtomicBool>,
+    ) -> mpsc::Receiver<Result<Vec<u8>, SvmError>> {
+        let limit = self.output_limit;
+        let exceeded = Arc::clone(exceeded);
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let read = || -> Result<Vec<u8>, SvmError> {
+                let mut output = Vec::new();
+                let Some(pipe) = pipe else {
+                    return Ok(output);
+                };
+                match limit {
+                    Some(limit) => {
+                        // Read one byte past the limit to tell whether it was exceeded.
+                        pipe.take(limit as u64 + 1).read_to_end(&mut output)?;
+                        if output.len() > limit {
+                            output.truncate(limit);
+                            exceeded.store(true, Ordering::Relaxed);
+                        }
+                    }
+                    None => {
+                        let mut pipe = pipe;
+                        pipe.read_to_end(&mut output)?;
+                    }
+                }
+                Ok(output)
+            };
+            // The receiver is gone if the output of a killed child was given up on.
+            let _ = tx.send(read());
+        });
+        rx
+    }
+
+    /// Returns the name of the binary used in errors.
+    fn name(&self) -> String {
+        self.solc_path.display().to_string()
+    }
+}
+
+/// Returns the output sent by a reader thread.
+///
+/// The pipes of a killed child stay open while any of its own children hold them, so its output
+/// is only waited for a short while and is otherwise given up on, as it's not returned anyway.
+fn collect(
+    output: &mpsc::Receiver<Result<Vec<u8>, SvmError>>,
+    killed: bool,
+) -> Result<Vec<u8>, SvmError> {
+    let output = if killed {
+        match output.recv_timeout(KILLED_OUTPUT_GRACE) {
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+            output => output.ok(),
+        }
+    } else {
+        output.recv().ok()
+    };
+    // The sender is only dropped without sending if the reader panicked.
+    output.unwrap_or_else(|| Err(std::io::Error::other("output reader panicked").into()))
+}
+
+/// Kills the child and reaps it.
+fn kill(child: &mut Child) -> Result<ExitStatus, SvmError> {
+    // The child may have exited in the meantime.
+    let _ = child.kill();
+    Ok(child.wait()?)
+}
+
+/// Limits the address space of the spawned child to `bytes` with `RLIMIT_AS`.
+#[cfg(target_os = "linux")]
+fn limit_address_space(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: `setrlimit` is async-signal-safe and the closure doesn't allocate.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+/// Returns whether the child failed because it couldn't allocate memory.
+///
+/// Solc reports `std::bad_alloc` when an allocation fails. Other crashes, e.g. an abort on a
+/// failed assertion, are reported through the exit status instead.
+fn is_out_of_memory(status: ExitStatus, stderr: &[u8]) -> bool {
+    if status.success() {
+        return false;
+    }
+    let stderr = String::from_utf8_lossy(stderr);
+    stderr.contains("bad_alloc")
+        || stderr.contains("out of memory")
+        || stderr.contains("memory allocation failed")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    fn script(dir: &Path, body: &str) -> PathBuf {
+        let path = dir.join("solc");
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn run_with_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(script(dir.path(), "cat\necho \"$1\" >&2\nexit 2"))
+            .timeout(Duration::from_secs(10))
+            .output_limit(1024);
+        let output = runner.run(["--standard-json"], b"{}").unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert_eq!(output.stdout, b"{}");
+        assert_eq!(output.stderr, b"--standard-json\n");
+    }
+
+    #[test]
+    fn run_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner =
+            Runner::new(script(dir.path(), "exec sleep 30")).timeout(Duration::from_millis(200));
+        let start = Instant::now();
+        let err = runner.run(["--version"], b"").unwrap_err();
+        assert!(matches!(err, SvmError::Timeout(_, 1)), "{err}");
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn run_timeout_with_grandchild() {
+        let dir = tempfile::tempdir().unwrap();
+        // The grandchild inherits the pipes and keeps them open after the child is killed.
+        let runner = Runner::new(script(dir.path(), "sleep 10 &\nexec sleep 30"))
+            .timeout(Duration::from_millis(200));
+        let start = Instant::now();
+        let err = runner.run(["--version"], b"").unwrap_err();
+        assert!(matches!(err, SvmError::Timeout(_, 1)), "{err}");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn run_output_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(script(dir.path(), "exec yes"))
+            .timeout(Duration::from_secs(10))
+            .output_limit(4096);
+        let err = runner.run(["--version"], b"").unwrap_err();
+        assert!(
+            matches!(err, SvmError::OutputLimitExceeded(_, 4096)),
+            "{err}"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn run_memory_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let runner = Runner::new(script(
+            dir.path(),
+            "echo 'terminate called after throwing an instance of std::bad_alloc' >&2\nexit 134",
+        ))
+        .memory_limit(512 * 1024 * 1024);
+        let err = runner.run(["--version"], b"").unwrap_err();
+        assert!(
+            matches!(err, SvmError::MemoryLimitExceeded(_, 536870912)),
+            "{err}"
+        );
+
+        // Crashes unrelated to memory are reported through the exit status.
+        let runner = Runner::new(script(
+            dir.path(),
+            "echo 'Assertion failed' >&2\nkill -ABRT $$",
+        ))
+        .memory_limit(512 * 1024 * 1024);
+        let output = runner.run(["--version"], b"").unwrap();
+        assert!(!output.status.success());
+
+        // The limit is applied to the child.
+        let runner = Runner::new(script(dir.path(), "ulimit -v")).memory_limit(64 * 1024 * 1024);
+        let output = runner.run(None::<&str>, b"").unwrap();
+        assert_eq!(output.stdout, b"65536\n");
+    }
+}
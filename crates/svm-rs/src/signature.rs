@@ -0,0 +1,208 @@
+use crate::SvmError;
+use ed25519_dalek::{Signature, VerifyingKey};
+use url::Url;
+
+/// Environment variable with a comma-separated list of hex-encoded ed25519 public keys that
+/// release lists may be signed with.
+///
+/// The detached signature of a release list is fetched from the mirror that served it, at the
+/// list's URL with a `.sig` suffix, e.g. `linux-amd64/list.json.sig`. It holds the 64-byte
+/// signature of the list, either raw or hex-encoded.
+///
+/// If set, release lists are only accepted with a signature valid for one of the keys, unless
+/// [`SVM_REQUIRE_SIGNATURES`] opts out of it.
+pub const SVM_RELEASES_PUBLIC_KEYS: &str = "SVM_RELEASES_PUBLIC_KEYS";
+
+/// Environment variable that, if set to `1` or `true`, rejects release lists without a valid
+/// signature by one of the [`SVM_RELEASES_PUBLIC_KEYS`], and if set to `0` or `false`, accepts
+/// unsigned release lists.
+///
+/// Signatures are required by default whenever [`SVM_RELEASES_PUBLIC_KEYS`] is set. Opting out
+/// only checks the lists that are signed: anyone able to tamper with a mirror can also remove the
+/// signature, which disables the verification altogether.
+pub const SVM_REQUIRE_SIGNATURES: &str = "SVM_REQUIRE_SIGNATURES";
+
+/// How the signatures of release lists are checked.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SignaturePolicy {
+    keys: Vec<VerifyingKey>,
+    required: bool,
+}
+
+impl SignaturePolicy {
+    /// Reads the policy from [`SVM_RELEASES_PUBLIC_KEYS`] and [`SVM_REQUIRE_SIGNATURES`].
+    pub(crate) fn from_env() -> Result<Self, SvmError> {
+        let keys = std::env::var(SVM_RELEASES_PUBLIC_KEYS).unwrap_or_default();
+        let required =
+            std::env::var(SVM_REQUIRE_SIGNATURES)
+                .ok()
+                .and_then(|required| match required.trim() {
+                    "1" | "true" => Some(true),
+                    "0" | "false" => Some(false),
+                    _ => None,
+                });
+        Self::new(&keys, required)
+    }
+
+    /// Creates a policy for the comma-separated hex-encoded `keys`.
+    ///
+    /// Signatures are required if `required` is unset and any key is given.
+    pub(crate) fn new(keys: &str, required: Option<bool>) -> Result<Self, SvmError> {
+        let keys = keys
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(parse_public_key)
+            .collect::<Result<Vec<_>, _>>()?;
+        let required = required.unwrap_or(!keys.is_empty());
+        Ok(Self { keys, required })
+    }
+
+    /// Returns whether signatures need to be fetched at all.
+    pub(crate) const fn is_enabled(&self) -> bool {
+        !self.keys.is_empty() || self.required
+    }
+
+    /// Checks the detached `signature` of the release list served at `url`, if any.
+    pub(crate) fn verify(
+        &self,
+        url: &Url,
+        list: &[u8],
+        signature: Option<&[u8]>,
+    ) -> Result<(), SvmError> {
+        let failed = |reason: &str| SvmError::SignatureVerificationFailed {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let Some(signature) = signature else {
+            if self.required {
+                return Err(failed("the release list is not signed"));
+            }
+            return Ok(());
+        };
+        if self.keys.is_empty() {
+            return Err(failed("no public keys are configured"));
+        }
+        let signature = parse_signature(signature).ok_or_else(|| failed("malformed signature"))?;
+        if self
+            .keys
+            .iter()
+            .any(|key| key.verify_strict(list, &signature).is_ok())
+        {
+            Ok(())
+        } else {
+            Err(failed("the signature doesn't match any of the public keys"))
+        }
+    }
+}
+
+/// Returns the URL of the detached signature of the release list at `url`.
+pub(crate) fn signature_url(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_path(&format!("{}.sig", url.path()));
+    url
+}
+
+fn parse_public_key(key: &str) -> Result<VerifyingKey, SvmError> {
+    let invalid = || SvmError::InvalidPublicKey(key.to_string());
+    let bytes = hex::decode(key.trim_start_matches("0x")).map_err(|_| invalid())?;
+    let bytes = bytes.try_into().map_err(|_| invalid())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| invalid())
+}
+
+fn parse_signature(signature: &[u8]) -> Option<Signature> {
+    let bytes = match <[u8; Signature::BYTE_SIZE]>::try_from(signature) {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let signature = std::str::from_utf8(signature).ok()?.trim();
+            hex::decode(signature.trim_start_matches("0x"))
+                .ok()?
+                .try_into()
+                .ok()?
+        }
+    };
+    Some(Signature::from_bytes(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const LIST: &[u8] = br#"{"builds":[],"releases":{}}"#;
+
+    fn policy_for(keys: &[&SigningKey], required: Option<bool>) -> SignaturePolicy {
+        let keys = keys
+            .iter()
+            .map(|key| hex::encode(key.verifying_key().as_bytes()))
+            .collect::<Vec<_>>()
+            .join(",");
+        SignaturePolicy::new(&keys, required).unwrap()
+    }
+
+    #[test]
+    fn verify_release_list() {
+        let url = Url::parse("https://solc.example.com/linux-amd64/list.json").unwrap();
+        let trusted = SigningKey::from_bytes(&[1; 32]);
+        let other = SigningKey::from_bytes(&[2; 32]);
+        let signature = trusted.sign(LIST).to_bytes();
+        let hex_signature = format!("0x{}\n", hex::encode(signature));
+
+        let policy = policy_for(&[&other, &trusted], None);
+        assert!(policy.is_enabled());
+        policy.verify(&url, LIST, Some(&signature)).unwrap();
+        policy
+            .verify(&url, LIST, Some(hex_signature.as_bytes()))
+            .unwrap();
+        // Signatures are required once keys are configured.
+        assert!(matches!(
+            policy.verify(&url, LIST, None),
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+        // Unless unsigned lists are explicitly accepted.
+        let lenient = policy_for(&[&other, &trusted], Some(false));
+        lenient.verify(&url, LIST, None).unwrap();
+        assert!(
+            lenient
+                .verify(&url, LIST, Some(&other.sign(b"other").to_bytes()))
+                .is_err()
+        );
+
+        let tampered = br#"{"builds":[],"releases":{"0.8.7":"solc"}}"#;
+        assert!(matches!(
+            policy.verify(&url, tampered, Some(&signature)),
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+        assert!(matches!(
+            policy.verify(&url, LIST, Some(b"not a signature")),
+            Err(SvmError::SignatureVerificationFailed { .. })
+        ));
+
+        let policy = policy_for(&[&other], Some(true));
+        assert!(policy.verify(&url, LIST, Some(&signature)).is_err());
+        assert!(policy.verify(&url, LIST, None).is_err());
+
+        let policy = policy_for(&[], None);
+        assert!(!policy.is_enabled());
+        policy.verify(&url, LIST, None).unwrap();
+        assert!(policy.verify(&url, LIST, Some(&signature)).is_err());
+    }
+
+    #[test]
+    fn invalid_public_key() {
+        assert!(matches!(
+            SignaturePolicy::new("0x1234", None),
+            Err(SvmError::InvalidPublicKey(_))
+        ));
+    }
+
+    #[test]
+    fn signature_urls() {
+        let url = Url::parse("https://solc.example.com/linux-amd64/list.json").unwrap();
+        assert_eq!(
+            signature_url(&url).as_str(),
+            "https://solc.example.com/linux-amd64/list.json.sig"
+        );
+    }
+}
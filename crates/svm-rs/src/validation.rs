@@ -0,0 +1,292 @@
+use crate::{Releases, SvmError};
+use semver::Version;
+use serde_json::Value;
+use std::{collections::BTreeSet, fmt};
+
+/// Environment variable that, if set to `1` or `true`, rejects fetched release lists with any
+/// [`ReleaseIssue`].
+///
+/// Lists that are known not to label their pre-releases are still accepted with
+/// [`ReleaseIssue::PrereleaseMismatch`].
+pub const SVM_STRICT_RELEASES: &str = "SVM_STRICT_RELEASES";
+
+/// An inconsistency in a release list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReleaseIssue {
+    /// A hash of a build isn't a hex-encoded 32-byte digest.
+    MalformedHash {
+        version: String,
+        field: &'static str,
+        value: String,
+    },
+    /// Several builds have the same version.
+    DuplicateBuild(Version),
+    /// A release has no build, so its checksum is unknown.
+    MissingBuild(Version),
+    /// A release names another artifact than the path of its build.
+    ArtifactMismatch {
+        version: Version,
+        release: String,
+        build: String,
+    },
+    /// The `prerelease` label of a build doesn't match the pre-release in its version.
+    PrereleaseMismatch {
+        version: Version,
+        label: Option<String>,
+    },
+    /// The latest release isn't one of the releases.
+    UnknownLatestRelease(Version),
+}
+
+impl fmt::Display for ReleaseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedHash {
+                version,
+                field,
+                value,
+            } => write!(f, "build {version} has a malformed {field}: {value:?}"),
+            Self::DuplicateBuild(version) => write!(f, "build {version} is listed several times"),
+            Self::MissingBuild(version) => {
+                write!(f, "release {version} has no build with its checksum")
+            }
+            Self::ArtifactMismatch {
+                version,
+                release,
+                build,
+            } => write!(
+                f,
+                "release {version} is {release}, but its build is {build}"
+            ),
+            Self::PrereleaseMismatch { version, label } => write!(
+                f,
+                "build {version} is labelled as pre-release {}",
+                label.as_deref().unwrap_or("<none>")
+            ),
+            Self::UnknownLatestRelease(version) => {
+                write!(f, "latest release {version} is not released")
+            }
+        }
+    }
+}
+
+impl Releases {
+    /// Returns the inconsistencies in the release list.
+    ///
+    /// Malformed hashes prevent a list from being parsed at all, use [`check_release_list`] to
+    /// find them.
+    pub fn validate(&self) -> Vec<ReleaseIssue> {
+        let mut issues = Vec::new();
+
+        let mut builds = BTreeSet::new();
+        for build in &self.builds {
+            // Official lists label pre-releases of a numeric version, e.g. `0.8.31` and `pre.1`.
+            if !builds.insert((
+                &build.version,
+                build.prerelease.as_deref(),
+                build.build.as_deref(),
+            )) {
+                issues.push(ReleaseIssue::DuplicateBuild(build.version.clone()));
+            }
+            if !build.version.pre.is_empty()
+                && build.prerelease.as_deref() != Some(build.version.pre.as_str())
+            {
+                issues.push(ReleaseIssue::PrereleaseMismatch {
+                    version: build.version.clone(),
+                    label: build.prerelease.clone(),
+                });
+            }
+            for (field, hash) in [
+                ("sha256", Some(&build.sha256)),
+                ("keccak256", build.keccak256.as_ref()),
+            ] {
+                if let Some(hash) = hash
+                    && hash.len() != 32
+                {
+                    issues.push(ReleaseIssue::MalformedHash {
+                        version: build.version.to_string(),
+                        field,
+                        value: hex::encode_prefixed(hash),
+                    });
+                }
+            }
+        }
+
+        for (version, artifact) in &self.releases {
+            match self.get_build_info(version) {
+                None => issues.push(ReleaseIssue::MissingBuild(version.clone())),
+                Some(build) => {
+                    if let Some(path) = &build.path
+                        && path != artifact
+                    {
+                        issues.push(ReleaseIssue::ArtifactMismatch {
+                            version: version.clone(),
+                            release: artifact.clone(),
+                            build: path.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(latest) = &self.latest_release
+            && !self.releases.contains_key(latest)
+        {
+            issues.push(ReleaseIssue::UnknownLatestRelease(latest.clone()));
+        }
+
+        issues
+    }
+}
+
+/// Parses a release list and returns its inconsistencies, including malformed hashes.
+///
+/// Returns an error if the list is not a release list at all.
+pub fn check_release_list(list: &[u8]) -> Result<Vec<ReleaseIssue>, SvmError> {
+    let mut value: Value = serde_json::from_slice(list)?;
+    let mut issues = Vec::new();
+
+    // Replace malformed hashes, which would otherwise fail to parse.
+    if let Some(builds) = value.get_mut("builds").and_then(Value::as_array_mut) {
+        for build in builds.iter_mut().filter_map(Value::as_object_mut) {
+            let version = build
+                .get("version")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            for field in ["sha256", "keccak256"] {
+                let hash = build.get(field).unwrap_or(&Value::Null);
+                if field == "keccak256" && hash.is_null() {
+                    continue;
+                }
+                if hash.as_str().is_none_or(|hash| hex::decode(hash).is_err()) {
+                    issues.push(ReleaseIssue::MalformedHash {
+                        version: version.clone(),
+                        field,
+                        value: hash
+                            .as_str()
+                            .map_or_else(|| hash.to_string(), str::to_string),
+                    });
+                    build.insert(field.to_string(), Value::from(hex::encode([0; 32])));
+                }
+            }
+        }
+    }
+
+    let releases: Releases = serde_json::from_value(value)?;
+    issues.extend(releases.validate());
+    Ok(issues)
+}
+
+/// Returns whether [`SVM_STRICT_RELEASES`] is set.
+pub(crate) fn is_strict() -> bool {
+    std::env::var(SVM_STRICT_RELEASES).is_ok_and(|strict| matches!(strict.trim(), "1" | "true"))
+}
+
+/// Returns an error if the release list fetched from `url` has any issue, ignoring pre-release
+/// label mismatches if the list is known not to label them.
+pub(crate) fn ensure_valid(
+    url: &str,
+    releases: &Releases,
+    unlabelled_prereleases: bool,
+) -> Result<(), SvmError> {
+    let issues = releases
+        .validate()
+        .into_iter()
+        .filter(|issue| {
+            !(unlabelled_prereleases && matches!(issue, ReleaseIssue::PrereleaseMismatch { .. }))
+        })
+        .map(|issue| issue.to_string())
+        .collect::<Vec<_>>();
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(SvmError::InvalidReleaseList {
+            url: url.to_string(),
+            issues: issues.join("; "),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "0x6d7a1ab7b2a1d9a5e9d07b3ed2b3a5b6e3a6a3bd2c8a0d6d0a2c45c5e3bdc4a1";
+
+    #[test]
+    fn valid_release_list() {
+        let list = format!(
+            r#"{{
+                "builds": [
+                    {{"path": "solc-v0.8.7", "version": "0.8.7", "sha256": "{SHA256}"}},
+                    {{"path": "solc-v0.8.31-pre.1", "version": "0.8.31", "prerelease": "pre.1", "sha256": "{SHA256}"}},
+                    {{"path": "solc-v0.8.31", "version": "0.8.31", "sha256": "{SHA256}"}},
+                    {{"path": "solc-v0.8.32-pre.1", "version": "0.8.32-pre.1", "prerelease": "pre.1", "sha256": "{SHA256}"}}
+                ],
+                "releases": {{"0.8.7": "solc-v0.8.7", "0.8.31": "solc-v0.8.31"}},
+                "latestRelease": "0.8.31"
+            }}"#
+        );
+        assert_eq!(check_release_list(list.as_bytes()).unwrap(), []);
+    }
+
+    #[test]
+    fn release_list_issues() {
+        let list = format!(
+            r#"{{
+                "builds": [
+                    {{"path": "solc-v0.8.7", "version": "0.8.7", "sha256": "{SHA256}"}},
+                    {{"path": "solc-v0.8.7", "version": "0.8.7", "sha256": "0xzz"}},
+                    {{"path": "solc-v0.8.8", "version": "0.8.8", "sha256": "0x1234"}},
+                    {{"path": "solc-v0.8.31-pre.1", "version": "0.8.31-pre.1", "sha256": "{SHA256}"}}
+                ],
+                "releases": {{"0.8.7": "solc-v0.8.7", "0.8.8": "solc-0.8.8", "0.8.9": "solc-v0.8.9"}},
+                "latestRelease": "0.8.10"
+            }}"#
+        );
+        let v = |version: &str| Version::parse(version).unwrap();
+        assert_eq!(
+            check_release_list(list.as_bytes()).unwrap(),
+            [
+                ReleaseIssue::MalformedHash {
+                    version: "0.8.7".to_string(),
+                    field: "sha256",
+                    value: "0xzz".to_string(),
+                },
+                ReleaseIssue::DuplicateBuild(v("0.8.7")),
+                ReleaseIssue::MalformedHash {
+                    version: "0.8.8".to_string(),
+                    field: "sha256",
+                    value: "0x1234".to_string(),
+                },
+                ReleaseIssue::PrereleaseMismatch {
+                    version: v("0.8.31-pre.1"),
+                    label: None,
+                },
+                ReleaseIssue::ArtifactMismatch {
+                    version: v("0.8.8"),
+                    release: "solc-0.8.8".to_string(),
+                    build: "solc-v0.8.8".to_string(),
+                },
+                ReleaseIssue::MissingBuild(v("0.8.9")),
+                ReleaseIssue::UnknownLatestRelease(v("0.8.10")),
+            ]
+        );
+        assert!(check_release_list(b"[]").is_err());
+    }
+
+    #[test]
+    fn strict_release_list() {
+        let releases: Releases = serde_json::from_str(&format!(
+            r#"{{"builds": [{{"path": "solc-v0.8.31-pre.1", "version": "0.8.31-pre.1", "sha256": "{SHA256}"}}], "releases": {{}}}}"#
+        ))
+        .unwrap();
+        ensure_valid("https://example.com/list.json", &releases, true).unwrap();
+        let err = ensure_valid("https://example.com/list.json", &releases, false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Release list https://example.com/list.json is invalid: build 0.8.31-pre.1 is labelled as pre-release <none>"
+        );
+    }
+}